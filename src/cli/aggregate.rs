@@ -1,26 +1,78 @@
-use crate::cli::ErrorKind;
-use crate::net::Mask;
+use crate::cli::json::{self, Document};
+use crate::cli::{table, template};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6, Mask};
 
 use super::arg_parser;
 
 pub struct CLI<W: std::io::Write> {
     pub out: W,
+    pub output: OutputFormat,
+    /// Comma separated columns of the csv, tsv and table outputs.
+    pub columns: Option<String>,
+    pub format: Option<String>,
 }
 
 impl<W: std::io::Write> CLI<W> {
     pub fn new(out: W) -> Self {
-        CLI { out }
+        CLI {
+            out,
+            output: OutputFormat::Text,
+            columns: None,
+            format: None,
+        }
     }
 
     pub fn execute(&mut self, raw_cidr: String, raw_mask: String) -> Result<(), ErrorKind> {
-        let cidr = arg_parser::parse_cidr("CIDR", raw_cidr)?;
-        let mask = arg_parser::parse_mask("MASK", raw_mask)?;
+        if arg_parser::is_ipv6(&raw_cidr) {
+            self.aggregate::<IPv6>(raw_cidr, raw_mask)
+        } else {
+            self.aggregate::<IPv4>(raw_cidr, raw_mask)
+        }
+    }
+
+    fn aggregate<A: AddressFamily>(
+        &mut self,
+        raw_cidr: String,
+        raw_mask: String,
+    ) -> Result<(), ErrorKind> {
+        let cidr = arg_parser::parse_cidr::<A>("CIDR", raw_cidr)?;
+        let mask = arg_parser::parse_mask::<A>("MASK", raw_mask)?;
+        let template = template::parse(self.format.as_deref())?;
 
         let start = mask.prefix_length().min(cidr.mask().prefix_length());
         let stop = mask.prefix_length().max(cidr.mask().prefix_length());
-        for mask_length in start..=stop {
-            let mask = Mask::new(mask_length).expect("{} is not a valid mask length");
-            writeln!(self.out, "{}", cidr.aggregate(mask)).unwrap();
+        let aggregates = (start..=stop).map(|mask_length| {
+            let mask = Mask::<A>::new(mask_length).expect("{} is not a valid mask length");
+            cidr.aggregate(mask)
+        });
+
+        if template.is_some() {
+            for aggregate in aggregates {
+                template::write_cidr(&mut self.out, template.as_ref(), &aggregate);
+            }
+            return Ok(());
+        }
+
+        if self.output.is_tabular() {
+            return table::write_cidrs(
+                &mut self.out,
+                self.output,
+                self.columns.as_deref(),
+                aggregates,
+            );
+        }
+
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("cidr", json::cidr(&cidr))
+                .array("aggregates", aggregates.map(|cidr| json::cidr(&cidr)))
+                .end();
+            return Ok(());
+        }
+
+        for aggregate in aggregates {
+            writeln!(self.out, "{}", aggregate).unwrap();
         }
 
         Ok(())
@@ -29,7 +81,7 @@ impl<W: std::io::Write> CLI<W> {
 
 #[cfg(test)]
 mod tests {
-    use crate::cli::ErrorKind;
+    use crate::cli::{ErrorKind, OutputFormat};
 
     #[test]
     fn aggregate_empty_cidr() {
@@ -138,4 +190,45 @@ mod tests {
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    fn aggregate_ipv6_64_to_60() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("2001:db8::1/64".to_string(), "60".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/aggregate-ipv6-64-to-60.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn aggregate_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("10.12.5.0/24".to_string(), "22".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/aggregate-24-to-22.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn aggregate_tsv() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Tsv;
+        cli.execute("10.12.5.0/24".to_string(), "22".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/aggregate-24-to-22.tsv");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
 }
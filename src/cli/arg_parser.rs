@@ -1,7 +1,14 @@
 use crate::cli::ErrorKind;
-use crate::net::{CIDRParsingError, Mask, MaskParsingError, CIDR};
+use crate::net::{
+    AddressFamily, CIDRParsingError, IPRange, IPRangeParsingError, IPSet, Mask, MaskParsingError,
+    SubnetRequirement, WildcardMask, CIDR,
+};
 
-pub fn parse_cidr(name: &str, raw: String) -> Result<CIDR, ErrorKind> {
+pub fn is_ipv6(raw: &str) -> bool {
+    raw.contains(':')
+}
+
+pub fn parse_cidr<A: AddressFamily>(name: &str, raw: String) -> Result<CIDR<A>, ErrorKind> {
     if raw.is_empty() {
         return Err(ErrorKind::InvalidInput(format!(
             "expecting non empty {} argument",
@@ -9,18 +16,21 @@ pub fn parse_cidr(name: &str, raw: String) -> Result<CIDR, ErrorKind> {
         )));
     }
 
-    match raw.parse::<CIDR>() {
+    match raw.parse::<CIDR<A>>() {
         Ok(cidr) => Ok(cidr),
-        Err(CIDRParsingError::InvalidMaskLength) => Err(ErrorKind::InvalidInput(
-            "masklength must be between 0 and 32".to_string(),
-        )),
-        Err(CIDRParsingError::InvalidHostFormat) => Err(ErrorKind::InvalidInput(
-            "invalid IPv4 CIDR format".to_string(),
-        )),
+        Err(CIDRParsingError::InvalidMaskLength) => Err(ErrorKind::InvalidInput(format!(
+            "masklength must be between 0 and {}",
+            A::BITS
+        ))),
+        Err(CIDRParsingError::InvalidHostFormat) => Err(ErrorKind::InvalidInput(format!(
+            "invalid {} CIDR format",
+            A::NAME
+        ))),
+        Err(CIDRParsingError::NonContiguousMask(bit)) => Err(non_contiguous_mask(bit)),
     }
 }
 
-pub fn parse_mask(name: &str, raw: String) -> Result<Mask, ErrorKind> {
+pub fn parse_mask<A: AddressFamily>(name: &str, raw: String) -> Result<Mask<A>, ErrorKind> {
     if raw.is_empty() {
         return Err(ErrorKind::InvalidInput(format!(
             "expecting non empty {} argument",
@@ -28,13 +38,186 @@ pub fn parse_mask(name: &str, raw: String) -> Result<Mask, ErrorKind> {
         )));
     }
 
-    match raw.parse::<Mask>() {
+    match raw.parse::<Mask<A>>() {
         Ok(mask) => Ok(mask),
-        Err(MaskParsingError::InvalidRange) => Err(ErrorKind::InvalidInput(
-            "mask length must be between 0 and 32".to_string(),
-        )),
+        Err(MaskParsingError::InvalidRange) => Err(ErrorKind::InvalidInput(format!(
+            "mask length must be between 0 and {}",
+            A::BITS
+        ))),
         Err(MaskParsingError::InvalidFormat) => {
             Err(ErrorKind::InvalidInput("invalid mask format".to_string()))
         }
+        Err(MaskParsingError::NonContiguous(bit)) => Err(non_contiguous_mask(bit)),
+    }
+}
+
+pub fn parse_ip<A: AddressFamily>(name: &str, raw: String) -> Result<A, ErrorKind> {
+    if raw.is_empty() {
+        return Err(ErrorKind::InvalidInput(format!(
+            "expecting non empty {} argument",
+            name
+        )));
+    }
+
+    raw.parse::<A>()
+        .map_err(|_| ErrorKind::InvalidInput(format!("invalid {} address format", A::NAME)))
+}
+
+pub fn parse_wildcard_mask<A: AddressFamily>(
+    name: &str,
+    raw: String,
+) -> Result<WildcardMask<A>, ErrorKind> {
+    if raw.is_empty() {
+        return Err(ErrorKind::InvalidInput(format!(
+            "expecting non empty {} argument",
+            name
+        )));
+    }
+
+    raw.parse::<WildcardMask<A>>()
+        .map_err(|_| ErrorKind::InvalidInput("invalid wildcard mask format".to_string()))
+}
+
+pub fn parse_range<A: AddressFamily>(name: &str, raw: String) -> Result<IPRange<A>, ErrorKind> {
+    if raw.is_empty() {
+        return Err(ErrorKind::InvalidInput(format!(
+            "expecting non empty {} argument",
+            name
+        )));
     }
+
+    match raw.parse::<IPRange<A>>() {
+        Ok(range) => Ok(range),
+        Err(IPRangeParsingError::InvalidFormat) => Err(ErrorKind::InvalidInput(
+            "invalid range format, expecting FIRST-LAST".to_string(),
+        )),
+        Err(IPRangeParsingError::InvalidAddressFormat) => Err(ErrorKind::InvalidInput(format!(
+            "invalid {} address format",
+            A::NAME
+        ))),
+        Err(IPRangeParsingError::ReversedBounds) => Err(ErrorKind::InvalidInput(
+            "range first address must not be after its last address".to_string(),
+        )),
+    }
+}
+
+/// Parses a comma separated list of CIDRs and ranges.
+pub fn parse_set<A: AddressFamily>(raw: String) -> Result<IPSet<A>, ErrorKind> {
+    if raw.trim().is_empty() {
+        return Err(ErrorKind::InvalidInput(
+            "expecting non empty SET argument".to_string(),
+        ));
+    }
+
+    raw.split(',')
+        .map(str::trim)
+        .map(|entry| {
+            if entry.contains('-') {
+                parse_range::<A>("SET", entry.to_string())
+            } else {
+                parse_cidr::<A>("SET", entry.to_string()).map(IPRange::from)
+            }
+        })
+        .collect()
+}
+
+/// Most subnets a plan lists, `NAME:COUNTxHOSTS` requirements included.
+pub const MAX_PLANNED_SUBNETS: u32 = 65536;
+
+/// Addresses and subnets of a plan's supernet left to the requirements not
+/// parsed yet. Subnets are planned largest first, which never fragments the
+/// supernet, so requirements fit as long as their addresses do.
+pub struct RequirementBudget {
+    addresses: u128,
+    subnets: u32,
+}
+
+impl RequirementBudget {
+    pub fn new<A: AddressFamily>(supernet: &CIDR<A>) -> Self {
+        RequirementBudget {
+            // saturates for the IPv6 /0, one address short of its size
+            addresses: supernet.mask().wildcard().bits().saturating_add(1),
+            subnets: MAX_PLANNED_SUBNETS,
+        }
+    }
+}
+
+/// Parses a `NAME:HOSTS` or `NAME:COUNTxHOSTS` subnet requirement, the
+/// latter being expanded to COUNT requirements named `NAME #1`, `NAME #2`...
+/// once the budget is known to have room for that many subnets.
+pub fn parse_requirement<A: AddressFamily>(
+    raw: &str,
+    supernet: &CIDR<A>,
+    budget: &mut RequirementBudget,
+) -> Result<Vec<SubnetRequirement>, ErrorKind> {
+    let invalid = || {
+        ErrorKind::InvalidInput(format!(
+            "invalid requirement {}, expecting NAME:HOSTS or NAME:COUNTxHOSTS",
+            raw
+        ))
+    };
+
+    let (name, size) = raw.rsplit_once(':').ok_or_else(invalid)?;
+    let name = name.trim();
+    let (count, hosts) = match size.trim().split_once('x') {
+        Some((count, hosts)) => (count.trim().parse::<u32>(), hosts.trim().parse::<u128>()),
+        None => (Ok(1), size.trim().parse::<u128>()),
+    };
+    let (Ok(count), Ok(hosts)) = (count, hosts) else {
+        return Err(invalid());
+    };
+    if name.is_empty() || count == 0 {
+        return Err(invalid());
+    }
+    if hosts == 0 {
+        return Err(ErrorKind::InvalidInput(format!(
+            "requirement {} must have at least one host",
+            name
+        )));
+    }
+
+    if count > budget.subnets {
+        return Err(ErrorKind::InvalidInput(format!(
+            "cannot allocate {}: plans are limited to {} subnets",
+            name, MAX_PLANNED_SUBNETS
+        )));
+    }
+    let addresses = Mask::<A>::for_hosts(hosts)
+        .map(|mask| {
+            1u128
+                .checked_shl(A::BITS as u32 - mask.prefix_length() as u32)
+                .unwrap_or(u128::MAX)
+        })
+        .and_then(|size| size.checked_mul(count as u128))
+        .filter(|addresses| *addresses <= budget.addresses)
+        .ok_or_else(|| {
+            ErrorKind::InvalidInput(format!(
+                "cannot allocate {} in {}: not enough free space",
+                name,
+                supernet.network_address()
+            ))
+        })?;
+    budget.subnets -= count;
+    budget.addresses -= addresses;
+
+    if count == 1 && !size.contains('x') {
+        return Ok(vec![SubnetRequirement {
+            name: name.to_string(),
+            hosts,
+        }]);
+    }
+
+    Ok((1..=count)
+        .map(|index| SubnetRequirement {
+            name: format!("{} #{}", name, index),
+            hosts,
+        })
+        .collect())
+}
+
+fn non_contiguous_mask(bit: u8) -> ErrorKind {
+    ErrorKind::InvalidInput(format!(
+        "mask must be contiguous but bit {} breaks contiguity",
+        bit
+    ))
 }
@@ -1,14 +1,25 @@
-use crate::net::CIDR;
+use crate::net::{AddressFamily, CIDRComparison, IPKind, CIDR};
 use core::fmt;
 
-pub struct CIDRFormatter {
-    pub cidr: CIDR,
+pub struct CIDRFormatter<A: AddressFamily> {
+    pub cidr: CIDR<A>,
     pub with_binary: bool,
 }
 
-impl fmt::Display for CIDRFormatter {
+// wide enough to hold the longest mask description ("<mask> = <length>")
+pub fn column_width<A: AddressFamily>() -> usize {
+    if A::BITS > 32 {
+        46
+    } else {
+        21
+    }
+}
+
+impl<A: AddressFamily> fmt::Display for CIDRFormatter<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "Address:   {:15}      ", format!("{}", self.cidr.ip()))?;
+        let width = column_width::<A>();
+
+        write!(f, "Address:   {:width$}", format!("{}", self.cidr.ip()))?;
         if self.with_binary {
             write!(f, "{:b}", self.cidr.ip())?;
         }
@@ -16,7 +27,7 @@ impl fmt::Display for CIDRFormatter {
 
         write!(
             f,
-            "Netmask:   {:20} ",
+            "Netmask:   {:width$}",
             format!(
                 "{} = {}",
                 self.cidr.mask(),
@@ -30,7 +41,7 @@ impl fmt::Display for CIDRFormatter {
 
         write!(
             f,
-            "Wildcard:  {:15}      ",
+            "Wildcard:  {:width$}",
             format!("{}", self.cidr.wildcard_mask())
         )?;
         if self.with_binary {
@@ -42,7 +53,7 @@ impl fmt::Display for CIDRFormatter {
 
         write!(
             f,
-            "Network:   {:18}   ",
+            "Network:   {:width$}",
             format!("{}", self.cidr.network_address())
         )?;
         if self.with_binary {
@@ -52,7 +63,7 @@ impl fmt::Display for CIDRFormatter {
 
         write!(
             f,
-            "HostMin:   {:18}   ",
+            "HostMin:   {:width$}",
             self.cidr
                 .first_address()
                 .map(|ip| format!("{}", ip))
@@ -65,7 +76,7 @@ impl fmt::Display for CIDRFormatter {
 
         write!(
             f,
-            "HostMax:   {:18}   ",
+            "HostMax:   {:width$}",
             self.cidr
                 .last_address()
                 .map(|ip| format!("{}", ip))
@@ -78,7 +89,7 @@ impl fmt::Display for CIDRFormatter {
 
         write!(
             f,
-            "Broadcast: {:18}   ",
+            "Broadcast: {:width$}",
             self.cidr
                 .broadcast_address()
                 .map(|ip| format!("{}", ip))
@@ -91,14 +102,53 @@ impl fmt::Display for CIDRFormatter {
         }
         writeln!(f)?;
 
-        write!(
-            f,
-            "Hosts/Net: {:10}           class {}, {}",
-            format!("{}", self.cidr.hosts()),
-            self.cidr.class(),
-            self.cidr.kind(),
-        )?;
+        write!(f, "Hosts/Net: {:width$}", format!("{}", self.cidr.hosts()))?;
+        if let Some(class) = self.cidr.class() {
+            write!(f, "class {}, ", class)?;
+        }
+        write!(f, "{}", self.cidr.kind())?;
+        writeln!(f)?;
+
+        let overlaps = self.cidr.special_purposes();
+        for overlap in &overlaps {
+            let relation = match overlap.relation {
+                CIDRComparison::Subset => "within",
+                CIDRComparison::Superset => "contains",
+                _ => "is",
+            };
+            let block = overlap.block;
+            // the separating space keeps long IPv4 blocks apart from their name
+            let width = width - 1;
+
+            writeln!(
+                f,
+                "Registry:  {:width$} {} ({})",
+                format!("{:10}{}", relation, block.network::<A>()),
+                block.name,
+                block.rfc,
+            )?;
+            writeln!(
+                f,
+                "Flags:     source {}, destination {}, forwardable {}, globally reachable {}",
+                yes_no(block.source),
+                yes_no(block.destination),
+                yes_no(block.forwardable),
+                yes_no(block.globally_reachable),
+            )?;
+        }
+
+        if !overlaps.is_empty() && self.cidr.has_public_remainder() {
+            writeln!(f, "Registry:  {:10}{}", "remainder", IPKind::Public)?;
+        }
+
+        Ok(())
+    }
+}
 
-        writeln!(f)
+fn yes_no(value: bool) -> &'static str {
+    if value {
+        "yes"
+    } else {
+        "no"
     }
 }
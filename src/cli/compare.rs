@@ -1,35 +1,84 @@
 use crate::cli::arg_parser;
-use crate::cli::ErrorKind;
-use crate::net::CIDRComparison;
+use crate::cli::json::{self, Document, Value};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, CIDRComparison, IPv4, IPv6};
 
 pub struct CLI<W: std::io::Write> {
     pub out: W,
+    pub output: OutputFormat,
 }
 
 impl<W: std::io::Write> CLI<W> {
     pub fn new(out: W) -> Self {
-        CLI { out }
+        CLI {
+            out,
+            output: OutputFormat::Text,
+        }
     }
 
     pub fn execute(&mut self, raw_cidr: String, raw_other: String) -> Result<(), ErrorKind> {
-        let cidr = arg_parser::parse_cidr("CIDR", raw_cidr)?;
-        let other = arg_parser::parse_cidr("OTHER_CIDR", raw_other)?;
-        let description = match cidr.compare(&other) {
-            CIDRComparison::Subset => "is a subset of",
-            CIDRComparison::Superset => "is a superset of",
-            CIDRComparison::Equals => "is the same as",
-            CIDRComparison::Different => "is in a different network than",
+        if arg_parser::is_ipv6(&raw_cidr) {
+            self.compare::<IPv6>(raw_cidr, raw_other)
+        } else {
+            self.compare::<IPv4>(raw_cidr, raw_other)
+        }
+    }
+
+    fn compare<A: AddressFamily>(
+        &mut self,
+        raw_cidr: String,
+        raw_other: String,
+    ) -> Result<(), ErrorKind> {
+        let cidr = arg_parser::parse_cidr::<A>("CIDR", raw_cidr)?;
+        let other = arg_parser::parse_cidr::<A>("OTHER_CIDR", raw_other)?;
+        let comparison = cidr.compare(&other);
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("cidr", json::cidr(&cidr))
+                .field("other", json::cidr(&other))
+                .field("relation", json::comparison(&comparison))
+                .field("adjacent", Value::Bool(cidr.is_adjacent_to(&other)))
+                .field("supernet", Value::string(cidr.common_supernet(&other)))
+                .field("mergeable", Value::Bool(cidr.merge(&other).is_some()))
+                .field("gap", Value::string(cidr.gap(&other)))
+                .end();
+            return Ok(());
+        }
+
+        let description = if cidr.is_adjacent_to(&other) {
+            "is adjacent to"
+        } else {
+            describe_comparison(&comparison)
         };
 
         writeln!(self.out, "{} {} {}", cidr, description, other,).unwrap();
 
+        let supernet = cidr.common_supernet(&other);
+        if cidr.merge(&other).is_some() {
+            writeln!(self.out, "Supernet:  {} (exact merge)", supernet).unwrap();
+        } else {
+            writeln!(self.out, "Supernet:  {}", supernet).unwrap();
+        }
+        if comparison == CIDRComparison::Different {
+            writeln!(self.out, "Gap:       {} addresses", cidr.gap(&other)).unwrap();
+        }
+
         Ok(())
     }
 }
 
+pub fn describe_comparison(comparison: &CIDRComparison) -> &'static str {
+    match comparison {
+        CIDRComparison::Subset => "is a subset of",
+        CIDRComparison::Superset => "is a superset of",
+        CIDRComparison::Equals => "is the same as",
+        CIDRComparison::Different => "is in a different network than",
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::cli::ErrorKind;
+    use crate::cli::{ErrorKind, OutputFormat};
 
     #[test]
     fn compare_empty_cidr1() {
@@ -165,4 +214,71 @@ mod tests {
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    fn on_mixed_families() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("2001:db8::/32".to_string(), "10.14.25.99/16".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv6 CIDR format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn on_ipv6_superset() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("2001:db8::/32".to_string(), "2001:db8:1::/48".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/compare-ipv6-superset.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn on_adjacent() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.12.22.0/24".to_string(), "10.12.23.0/24".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/compare-adjacent.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn on_adjacent_not_mergeable() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.12.23.0/24".to_string(), "10.12.24.0/24".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/compare-adjacent-not-mergeable.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn on_adjacent_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("10.12.22.0/24".to_string(), "10.12.23.0/24".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/compare-adjacent.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
 }
@@ -0,0 +1,386 @@
+use crate::cli::ErrorKind;
+use crate::net::{AddressFamily, IPv4, IPv6, Mask, CIDR};
+
+use super::arg_parser;
+
+/// `KEY=value` lines of the Red Hat ipcalc output, displayed in this order.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub enum Variable {
+    Netmask,
+    Prefix,
+    Broadcast,
+    Network,
+    MinAddr,
+    MaxAddr,
+    Addresses,
+    AddrSpace,
+    /// Resolved through a reverse DNS lookup, so only displayed on request.
+    Hostname,
+}
+
+const VARIABLES: [Variable; 8] = [
+    Variable::Netmask,
+    Variable::Prefix,
+    Variable::Broadcast,
+    Variable::Network,
+    Variable::MinAddr,
+    Variable::MaxAddr,
+    Variable::Addresses,
+    Variable::AddrSpace,
+];
+
+/// Address space of the most specific special-purpose block Red Hat ipcalc
+/// names.
+fn address_space<A: AddressFamily>(ip: &A) -> &'static str {
+    A::special_purpose_registry()
+        .iter()
+        .filter(|block| block.address_space.is_some() && block.contains(ip))
+        .max_by_key(|block| block.prefix_length)
+        .and_then(|block| block.address_space)
+        .unwrap_or(A::DEFAULT_ADDRESS_SPACE)
+}
+
+impl Variable {
+    fn key(&self) -> &'static str {
+        match self {
+            Variable::Netmask => "NETMASK",
+            Variable::Prefix => "PREFIX",
+            Variable::Broadcast => "BROADCAST",
+            Variable::Network => "NETWORK",
+            Variable::MinAddr => "MINADDR",
+            Variable::MaxAddr => "MAXADDR",
+            Variable::Addresses => "ADDRESSES",
+            Variable::AddrSpace => "ADDRSPACE",
+            Variable::Hostname => "HOSTNAME",
+        }
+    }
+
+    /// Whether the variable only depends on the address, so that it can be
+    /// displayed without netmask or prefix.
+    fn is_address_only(&self) -> bool {
+        matches!(self, Variable::AddrSpace | Variable::Hostname)
+    }
+
+    fn value<A: AddressFamily>(
+        &self,
+        cidr: &CIDR<A>,
+        resolve: Resolver,
+    ) -> Result<Option<String>, ErrorKind> {
+        let value = match self {
+            Variable::Netmask => Some(cidr.mask().to_string()),
+            Variable::Prefix => Some(cidr.mask().prefix_length().to_string()),
+            Variable::Broadcast => cidr.broadcast_address().map(|ip| ip.to_string()),
+            Variable::Network => Some(cidr.network_address().ip().to_string()),
+            Variable::MinAddr => cidr.first_address().map(|ip| ip.to_string()),
+            Variable::MaxAddr => cidr.last_address().map(|ip| ip.to_string()),
+            Variable::Addresses => Some(cidr.hosts().to_string()),
+            Variable::AddrSpace => Some(address_space(&cidr.ip()).to_string()),
+            Variable::Hostname => Some(hostname(&cidr.ip(), resolve)?),
+        };
+
+        Ok(value)
+    }
+}
+
+/// Reverse DNS lookup, returning the numeric form of unknown addresses.
+pub type Resolver = fn(&std::net::IpAddr) -> std::io::Result<String>;
+
+/// Name of the address in the DNS, failing when only its numeric form is
+/// found like Red Hat ipcalc.
+fn hostname<A: AddressFamily>(ip: &A, resolve: Resolver) -> Result<String, ErrorKind> {
+    let not_found = || ErrorKind::CheckFailed(format!("cannot find hostname for {}", ip));
+    let address: std::net::IpAddr = ip.to_string().parse().map_err(|_| not_found())?;
+
+    match resolve(&address) {
+        Ok(hostname) if hostname.parse::<std::net::IpAddr>().is_err() => Ok(hostname),
+        _ => Err(not_found()),
+    }
+}
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    /// Every variable is displayed when empty.
+    pub variables: Vec<Variable>,
+    /// Only validates the address and its mask, displaying nothing.
+    pub check: bool,
+    /// Looks `HOSTNAME` up, the system resolver by default.
+    pub resolve: Resolver,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            variables: Vec::new(),
+            check: false,
+            resolve: dns_lookup::lookup_addr,
+        }
+    }
+
+    fn is_address_only(&self) -> bool {
+        !self.variables.is_empty() && self.variables.iter().all(Variable::is_address_only)
+    }
+
+    /// The address is either in the CIDR notation or followed by its netmask,
+    /// like `ipcalc -n 192.168.1.5 255.255.255.0`.
+    pub fn execute(
+        &mut self,
+        raw_address: String,
+        raw_netmask: Option<String>,
+    ) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_address) {
+            self.print::<IPv6>(raw_address, raw_netmask)
+        } else {
+            self.print::<IPv4>(raw_address, raw_netmask)
+        }
+    }
+
+    fn print<A: AddressFamily>(
+        &mut self,
+        raw_address: String,
+        raw_netmask: Option<String>,
+    ) -> Result<(), ErrorKind> {
+        let cidr = match (raw_address.contains('/'), raw_netmask) {
+            (true, Some(_)) => {
+                return Err(ErrorKind::InvalidInput(
+                    "both netmask and prefix specified, use one or the other".to_string(),
+                ))
+            }
+            (true, None) => arg_parser::parse_cidr::<A>("ADDRESS", raw_address)?,
+            (false, raw_netmask) => {
+                let ip = arg_parser::parse_ip::<A>("ADDRESS", raw_address)?;
+                let mask = match raw_netmask {
+                    Some(raw_netmask) => arg_parser::parse_mask::<A>("NETMASK", raw_netmask)?,
+                    None if self.check || self.is_address_only() => Mask::new(A::BITS).unwrap(),
+                    None => {
+                        return Err(ErrorKind::InvalidInput(
+                            "netmask or prefix expected".to_string(),
+                        ))
+                    }
+                };
+                CIDR::new(ip, mask)
+            }
+        };
+
+        if self.check {
+            return Ok(());
+        }
+
+        let mut variables = if self.variables.is_empty() {
+            VARIABLES.to_vec()
+        } else {
+            self.variables.clone()
+        };
+        variables.sort();
+        variables.dedup();
+
+        // Values are computed first so that a failed hostname lookup
+        // displays nothing.
+        let mut values = Vec::new();
+        for variable in variables {
+            if let Some(value) = variable.value(&cidr, self.resolve)? {
+                values.push((variable, value));
+            }
+        }
+
+        for (variable, value) in values {
+            if value.contains(char::is_whitespace) {
+                writeln!(self.out, "{}=\"{}\"", variable.key(), value).unwrap();
+            } else {
+                writeln!(self.out, "{}={}", variable.key(), value).unwrap();
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Variable;
+    use crate::cli::ErrorKind;
+    use std::net::IpAddr;
+
+    #[test]
+    fn compat_all_variables() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("192.168.1.5/24".to_string(), None).unwrap();
+
+        let expected_output = include_str!("testdata/compat.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn compat_selected_variables() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.variables = vec![Variable::Broadcast, Variable::Network, Variable::Broadcast];
+        cli.execute("192.168.1.5".to_string(), Some("255.255.255.0".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            "BROADCAST=192.168.1.255\nNETWORK=192.168.1.0\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn compat_ipv6() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.variables = vec![
+            Variable::Network,
+            Variable::Netmask,
+            Variable::Broadcast,
+            Variable::AddrSpace,
+        ];
+        cli.execute("2001:db8::1/64".to_string(), None).unwrap();
+
+        assert_eq!(
+            "NETMASK=ffff:ffff:ffff:ffff::
+NETWORK=2001:db8::
+ADDRSPACE=Documentation
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn compat_addrspace() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.variables = vec![Variable::AddrSpace];
+        for address in [
+            "0.1.2.3",
+            "10.1.2.3",
+            "100.64.0.1",
+            "127.0.0.1",
+            "169.254.1.1",
+            "192.0.0.9",
+            "192.0.2.1",
+            "192.88.99.1",
+            "198.18.0.1",
+            "198.51.100.1",
+            "203.0.113.1",
+            "224.0.0.1",
+            "240.0.0.1",
+            "255.255.255.255",
+            "8.8.8.8",
+            "::1",
+            "::ffff:10.0.0.1",
+            "2001::1",
+            "2001:5::1",
+            "2001:db8::1",
+            "fd00::1",
+            "fe80::1",
+            "ff02::1",
+            "2606:4700::1",
+        ] {
+            cli.execute(address.to_string(), None).unwrap();
+        }
+
+        let expected_output = include_str!("testdata/compat-addrspace.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    fn resolve(address: &IpAddr) -> std::io::Result<String> {
+        match address.to_string().as_str() {
+            "192.0.2.1" => Ok("router.example.com".to_string()),
+            "192.0.2.2" => Ok("192.0.2.2".to_string()),
+            _ => Err(std::io::Error::from(std::io::ErrorKind::NotFound)),
+        }
+    }
+
+    #[test]
+    fn compat_hostname() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.resolve = resolve;
+        cli.variables = vec![Variable::Hostname, Variable::Network];
+        cli.execute("192.0.2.1/24".to_string(), None).unwrap();
+        cli.variables = vec![Variable::Hostname];
+        cli.execute("192.0.2.1".to_string(), None).unwrap();
+
+        assert_eq!(
+            "NETWORK=192.0.2.0
+HOSTNAME=router.example.com
+HOSTNAME=router.example.com
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn compat_hostname_not_found() {
+        for address in ["192.0.2.2/24", "192.0.2.3/24"] {
+            let mut output = Vec::new();
+            let mut cli = super::CLI::new(&mut output);
+            cli.resolve = resolve;
+            cli.variables = vec![Variable::Network, Variable::Hostname];
+            let result = cli.execute(address.to_string(), None);
+
+            assert_eq!(
+                Err(ErrorKind::CheckFailed(format!(
+                    "cannot find hostname for {}",
+                    address.trim_end_matches("/24")
+                ))),
+                result
+            );
+            assert!(output.is_empty());
+        }
+    }
+
+    #[test]
+    fn compat_missing_netmask() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.variables = vec![Variable::Network];
+        let result = cli.execute("192.168.1.5".to_string(), None);
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "netmask or prefix expected".to_string()
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn compat_netmask_and_prefix() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let result = cli.execute(
+            "192.168.1.5/24".to_string(),
+            Some("255.255.255.0".to_string()),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "both netmask and prefix specified, use one or the other".to_string()
+            )),
+            result
+        );
+    }
+
+    #[test]
+    fn compat_check() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.check = true;
+        cli.execute("192.168.1.5".to_string(), None).unwrap();
+        let result = cli.execute("192.168.1.256".to_string(), None);
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 address format".to_string()
+            )),
+            result
+        );
+        assert!(output.is_empty());
+    }
+}
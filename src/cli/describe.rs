@@ -1,11 +1,18 @@
 use crate::cli::cidr_formatter::CIDRFormatter;
-use crate::cli::ErrorKind;
+use crate::cli::json::{self, Document};
+use crate::cli::template;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6, Mask, CIDR};
 
 use super::arg_parser;
 
 pub struct CLI<W: std::io::Write> {
     pub out: W,
     pub with_binary: bool,
+    /// Replaces the CIDR mask by the one suggested for this number of hosts.
+    pub hosts: Option<u128>,
+    pub output: OutputFormat,
+    pub format: Option<String>,
 }
 
 impl<W: std::io::Write> CLI<W> {
@@ -13,11 +20,42 @@ impl<W: std::io::Write> CLI<W> {
         CLI {
             out,
             with_binary: true,
+            hosts: None,
+            output: OutputFormat::Text,
+            format: None,
         }
     }
 
     pub fn execute(&mut self, raw_cidr: String) -> Result<(), ErrorKind> {
-        let cidr = arg_parser::parse_cidr("CIDR", raw_cidr)?;
+        if arg_parser::is_ipv6(&raw_cidr) {
+            self.describe::<IPv6>(raw_cidr)
+        } else {
+            self.describe::<IPv4>(raw_cidr)
+        }
+    }
+
+    fn describe<A: AddressFamily>(&mut self, raw_cidr: String) -> Result<(), ErrorKind> {
+        let mut cidr = arg_parser::parse_cidr::<A>("CIDR", raw_cidr)?;
+        let template = template::parse(self.format.as_deref())?;
+        if let Some(hosts) = self.hosts {
+            let mask = Mask::for_hosts(hosts).ok_or_else(|| {
+                ErrorKind::InvalidInput(format!("no {} network can hold {} hosts", A::NAME, hosts))
+            })?;
+            cidr = CIDR::new(cidr.ip(), mask);
+        }
+
+        if template.is_some() {
+            template::write_cidr(&mut self.out, template.as_ref(), &cidr);
+            return Ok(());
+        }
+
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("cidr", json::cidr(&cidr))
+                .end();
+            return Ok(());
+        }
+
         let formatter = CIDRFormatter {
             cidr,
             with_binary: self.with_binary,
@@ -31,7 +69,7 @@ impl<W: std::io::Write> CLI<W> {
 
 #[cfg(test)]
 mod tests {
-    use crate::cli::ErrorKind;
+    use crate::cli::{ErrorKind, OutputFormat};
 
     #[test]
     fn describe_empty_cidr() {
@@ -86,4 +124,183 @@ mod tests {
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    fn describe_unparsable_ipv6_cidr() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("2001:db8::zz/64".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv6 CIDR format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn describe_invalid_ipv6_mask() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("2001:db8::1/129".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "masklength must be between 0 and 128".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn describe_ipv6() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("2001:db8:abcd:12::42/48".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/describe-ipv6.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_non_contiguous_mask() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.12.23.43/255.255.0.255".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "mask must be contiguous but bit 25 breaks contiguity".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn describe_dotted_netmask() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.12.23.43/255.255.240.0".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/describe.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_special_purpose_overlaps() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.with_binary = false;
+        cli.execute("172.0.0.0/8".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/describe-overlaps.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_point_to_point_link() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.with_binary = false;
+        cli.execute("198.51.100.0/31".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/describe-31.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_suggested_mask() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.hosts = Some(4000);
+        cli.execute("10.12.23.43".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/describe.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_suggested_mask_too_many_hosts() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.hosts = Some(1 << 32);
+        let output = cli.execute("10.12.23.43".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "no IPv4 network can hold 4294967296 hosts".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn describe_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("10.12.23.43/20".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/describe.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_ipv6_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("2001:db8::1/64".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/describe-ipv6.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn describe_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{network}/{prefix} {first}-{last} {hosts}".to_string());
+        cli.execute("10.12.23.43/20".to_string()).unwrap();
+
+        assert_eq!(
+            "10.12.16.0/20 10.12.16.1-10.12.31.254 4094\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn describe_unknown_placeholder() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{network} {size}".to_string());
+        let result = cli.execute("10.12.23.43/20".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "unknown placeholder {size} in format, expecting one of {address}, {cidr}, \
+                {network}, {prefix}, {netmask}, {wildcard}, {first}, {last}, {broadcast}, \
+                {hosts}, {class}, {kind}"
+                    .to_string()
+            )),
+            result
+        );
+        assert!(output.is_empty());
+    }
 }
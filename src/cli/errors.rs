@@ -1,4 +1,6 @@
 #[derive(Debug, PartialEq)]
 pub enum ErrorKind {
     InvalidInput(String),
+    /// The input is valid but didn't pass the requested check.
+    CheckFailed(String),
 }
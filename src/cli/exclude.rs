@@ -0,0 +1,162 @@
+use crate::cli::json::{self, Document};
+use crate::cli::template;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    pub fn execute(
+        &mut self,
+        raw_supernet: String,
+        raw_excluded: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_supernet) {
+            self.exclude::<IPv6>(raw_supernet, raw_excluded)
+        } else {
+            self.exclude::<IPv4>(raw_supernet, raw_excluded)
+        }
+    }
+
+    fn exclude<A: AddressFamily>(
+        &mut self,
+        raw_supernet: String,
+        raw_excluded: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        let supernet = arg_parser::parse_cidr::<A>("SUPERNET", raw_supernet)?;
+        let template = template::parse(self.format.as_deref())?;
+        if raw_excluded.is_empty() {
+            return Err(ErrorKind::InvalidInput(
+                "expecting at least one EXCLUDED argument".to_string(),
+            ));
+        }
+
+        let excluded = raw_excluded
+            .into_iter()
+            .map(|raw| arg_parser::parse_cidr::<A>("EXCLUDED", raw))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("supernet", json::cidr(&supernet))
+                .array("cidrs", supernet.exclude(&excluded).iter().map(json::cidr))
+                .end();
+            return Ok(());
+        }
+
+        for cidr in supernet.exclude(&excluded) {
+            template::write_cidr(&mut self.out, template.as_ref(), &cidr);
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    #[test]
+    fn exclude_empty_supernet() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("".to_string(), vec!["10.0.4.0/22".to_string()]);
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty SUPERNET argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn exclude_nothing() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.0/16".to_string(), vec![]);
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting at least one EXCLUDED argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn exclude_invalid_excluded() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "10.0.0.0/16".to_string(),
+            vec!["10.0.4.0/22".to_string(), "2001:db8::/32".to_string()],
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 CIDR format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn exclude_prefixes() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(
+            "10.0.0.0/16".to_string(),
+            vec!["10.0.4.0/22".to_string(), "10.0.200.0/24".to_string()],
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/exclude.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn exclude_ipv6() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(
+            "2001:db8::/32".to_string(),
+            vec!["2001:db8:8000::/33".to_string()],
+        )
+        .unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!("2001:db8::/33\n", actual_output);
+    }
+
+    #[test]
+    fn exclude_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("10.0.0.0/24".to_string(), vec!["10.0.0.0/26".to_string()])
+            .unwrap();
+
+        let expected_output = include_str!("testdata/exclude.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+}
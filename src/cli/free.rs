@@ -0,0 +1,266 @@
+use crate::cli::json::{self, Document, Value};
+use crate::cli::template;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, FreeSpace, IPv4, IPv6, Mask, CIDR};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write, E: std::io::Write> {
+    pub out: W,
+    /// Receives the warnings about used prefixes outside the parent network.
+    pub err: E,
+    /// Only list the free networks using this mask.
+    pub mask: Option<String>,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write, E: std::io::Write> CLI<W, E> {
+    pub fn new(out: W, err: E) -> Self {
+        CLI {
+            out,
+            err,
+            mask: None,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    /// Blank used prefixes and ones starting with `#` are ignored.
+    pub fn execute(&mut self, raw_parent: String, raw_used: Vec<String>) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_parent) {
+            self.free::<IPv6>(raw_parent, raw_used)
+        } else {
+            self.free::<IPv4>(raw_parent, raw_used)
+        }
+    }
+
+    fn free<A: AddressFamily>(
+        &mut self,
+        raw_parent: String,
+        raw_used: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        let parent = arg_parser::parse_cidr::<A>("PARENT", raw_parent)?;
+        let template = template::parse(self.format.as_deref())?;
+        let mask = self
+            .mask
+            .clone()
+            .map(|raw_mask| arg_parser::parse_mask::<A>("MASK", raw_mask))
+            .transpose()?;
+
+        let used = raw_used
+            .iter()
+            .map(|raw| raw.trim())
+            .filter(|raw| !raw.is_empty() && !raw.starts_with('#'))
+            .map(|raw| arg_parser::parse_cidr::<A>("USED", raw.to_string()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let free = FreeSpace::new(&parent, &used);
+        for cidr in free.outside() {
+            writeln!(
+                self.err,
+                "warning: {} is not within {}",
+                cidr,
+                free.parent()
+            )
+            .unwrap();
+        }
+
+        if self.output == OutputFormat::Json {
+            self.write_json(&free, mask);
+            return Ok(());
+        }
+
+        if template.is_some() {
+            let cidrs: Box<dyn Iterator<Item = CIDR<A>>> = match mask {
+                Some(mask) => Box::new(free.blocks(mask)),
+                None => Box::new(free.cidrs()),
+            };
+            for cidr in cidrs {
+                template::write_cidr(&mut self.out, template.as_ref(), &cidr);
+            }
+            return Ok(());
+        }
+
+        let counts = match mask {
+            Some(mask) => {
+                let mut count = 0;
+                for cidr in free.blocks(mask) {
+                    writeln!(self.out, "{}", cidr).unwrap();
+                    count += 1;
+                }
+                vec![(mask, count)]
+            }
+            None => {
+                for cidr in free.cidrs() {
+                    writeln!(self.out, "{}", cidr).unwrap();
+                }
+                free.counts()
+            }
+        };
+
+        writeln!(self.out, "=>").unwrap();
+        for (mask, count) in counts {
+            writeln!(
+                self.out,
+                "{:11}{}",
+                format!("/{}:", mask.prefix_length()),
+                count
+            )
+            .unwrap();
+        }
+        writeln!(self.out, "{:11}{}", "Addresses:", free.addresses()).unwrap();
+
+        Ok(())
+    }
+
+    fn write_json<A: AddressFamily>(&mut self, free: &FreeSpace<A>, mask: Option<Mask<A>>) {
+        let mut document = Document::new(&mut self.out);
+        document.field("parent", json::cidr(&free.parent()));
+
+        let counts = match mask {
+            Some(mask) => {
+                let mut count = 0;
+                document.array(
+                    "cidrs",
+                    free.blocks(mask).map(|cidr| {
+                        count += 1;
+                        json::cidr(&cidr)
+                    }),
+                );
+                vec![(mask, count)]
+            }
+            None => {
+                document.array("cidrs", free.cidrs().map(|cidr| json::cidr(&cidr)));
+                free.counts()
+            }
+        };
+
+        let counts = counts.into_iter().map(|(mask, count)| {
+            Value::Object(vec![
+                ("prefix", Value::Number(mask.prefix_length() as u64)),
+                ("count", Value::string(count)),
+            ])
+        });
+        document
+            .field("counts", Value::Array(counts.collect()))
+            .field("addresses", Value::string(free.addresses()))
+            .end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    fn used(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|cidr| cidr.to_string()).collect()
+    }
+
+    #[test]
+    fn free_empty_parent() {
+        let (mut output, mut warnings) = (Vec::new(), Vec::new());
+        let mut cli = super::CLI::new(&mut output, &mut warnings);
+        let output = cli.execute("".to_string(), used(&["10.0.0.0/24"]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty PARENT argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn free_unparsable_used() {
+        let (mut output, mut warnings) = (Vec::new(), Vec::new());
+        let mut cli = super::CLI::new(&mut output, &mut warnings);
+        let output = cli.execute("10.20.0.0/16".to_string(), used(&["10.20.0.0/24", "nope"]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 CIDR format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn free_largest_blocks() {
+        let (mut output, mut warnings) = (Vec::new(), Vec::new());
+        let mut cli = super::CLI::new(&mut output, &mut warnings);
+        cli.execute(
+            "10.20.0.0/16".to_string(),
+            used(&[
+                "# allocated",
+                "10.20.0.0/24",
+                "",
+                "10.20.2.0/23",
+                "10.20.128.0/17",
+                "10.21.0.0/24",
+            ]),
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/free.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+        assert_eq!(
+            "warning: 10.21.0.0/24 is not within 10.20.0.0/16\n",
+            String::from_utf8(warnings).unwrap()
+        );
+    }
+
+    #[test]
+    fn free_blocks_of_mask() {
+        let (mut output, mut warnings) = (Vec::new(), Vec::new());
+        let mut cli = super::CLI::new(&mut output, &mut warnings);
+        cli.mask = Some("24".to_string());
+        cli.execute(
+            "10.20.0.0/20".to_string(),
+            used(&[
+                "10.20.0.0/24",
+                "10.20.2.0/23",
+                "10.20.8.0/21",
+                "10.20.5.128/25",
+            ]),
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/free-mask.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn free_json() {
+        let (mut output, mut warnings) = (Vec::new(), Vec::new());
+        let mut cli = super::CLI::new(&mut output, &mut warnings);
+        cli.mask = Some("26".to_string());
+        cli.output = OutputFormat::Json;
+        cli.execute("10.0.0.0/24".to_string(), used(&["10.0.0.0/26"]))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/free-mask.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn free_format() {
+        let (mut output, mut warnings) = (Vec::new(), Vec::new());
+        let mut cli = super::CLI::new(&mut output, &mut warnings);
+        cli.format = Some("{network} {netmask}".to_string());
+        cli.execute("10.0.0.0/24".to_string(), used(&["10.0.0.0/26"]))
+            .unwrap();
+
+        assert_eq!(
+            "10.0.0.64 255.255.255.192\n10.0.0.128 255.255.255.128\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
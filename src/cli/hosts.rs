@@ -0,0 +1,207 @@
+use crate::cli::json::{self, Document, Value};
+use crate::cli::template;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6, CIDR};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub all: bool,
+    pub reverse: bool,
+    pub stride: usize,
+    pub offset: usize,
+    pub limit: Option<usize>,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            all: false,
+            reverse: false,
+            stride: 1,
+            offset: 0,
+            limit: None,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    pub fn execute(&mut self, raw_cidr: String) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_cidr) {
+            self.hosts::<IPv6>(raw_cidr)
+        } else {
+            self.hosts::<IPv4>(raw_cidr)
+        }
+    }
+
+    fn hosts<A: AddressFamily>(&mut self, raw_cidr: String) -> Result<(), ErrorKind> {
+        let cidr = arg_parser::parse_cidr::<A>("CIDR", raw_cidr)?;
+        let template = template::parse(self.format.as_deref())?;
+        if self.stride == 0 {
+            return Err(ErrorKind::InvalidInput(
+                "stride must be greater than 0".to_string(),
+            ));
+        }
+
+        let addresses = if self.all {
+            cidr.addresses()
+        } else {
+            cidr.host_addresses()
+        };
+        let addresses: Box<dyn Iterator<Item = A>> = if self.reverse {
+            Box::new(addresses.rev())
+        } else {
+            Box::new(addresses)
+        };
+
+        let addresses = addresses
+            .skip(self.offset)
+            .step_by(self.stride)
+            .take(self.limit.unwrap_or(usize::MAX));
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("cidr", json::cidr(&cidr))
+                .array("hosts", addresses.map(Value::string))
+                .end();
+            return Ok(());
+        }
+
+        if template.is_some() {
+            for address in addresses {
+                let host = CIDR::new(address, cidr.mask());
+                template::write_cidr(&mut self.out, template.as_ref(), &host);
+            }
+            return Ok(());
+        }
+
+        for address in addresses {
+            writeln!(self.out, "{}", address).unwrap();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    #[test]
+    fn hosts_empty_cidr() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty CIDR argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn hosts_zero_stride() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.stride = 0;
+        let output = cli.execute("10.0.0.0/24".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "stride must be greater than 0".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn hosts_usable() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("192.168.1.42/29".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/hosts.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn hosts_all_reversed() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.all = true;
+        cli.reverse = true;
+        cli.execute("192.168.1.42/30".to_string()).unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(
+            "192.168.1.43\n192.168.1.42\n192.168.1.41\n192.168.1.40\n",
+            actual_output
+        );
+    }
+
+    #[test]
+    fn hosts_offset_limit_stride() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.offset = 9;
+        cli.stride = 10;
+        cli.limit = Some(3);
+        cli.execute("10.0.0.0/8".to_string()).unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!("10.0.0.10\n10.0.0.20\n10.0.0.30\n", actual_output);
+    }
+
+    #[test]
+    fn hosts_ipv6_large_network() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.reverse = true;
+        cli.limit = Some(2);
+        cli.execute("2001:db8::/32".to_string()).unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(
+            "2001:db8:ffff:ffff:ffff:ffff:ffff:ffff\n2001:db8:ffff:ffff:ffff:ffff:ffff:fffe\n",
+            actual_output
+        );
+    }
+
+    #[test]
+    fn hosts_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("192.168.1.42/30".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/hosts.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn hosts_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{address} {netmask} {broadcast}".to_string());
+        cli.execute("192.168.1.42/30".to_string()).unwrap();
+
+        assert_eq!(
+            "192.168.1.41 255.255.255.252 192.168.1.43
+192.168.1.42 255.255.255.252 192.168.1.43
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
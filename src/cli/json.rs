@@ -0,0 +1,262 @@
+use crate::net::{AddressFamily, CIDRComparison, CIDR};
+use core::fmt;
+
+/// Version of the JSON documents, bumped whenever a field is renamed,
+/// removed or changes type. Adding a field doesn't change the version.
+/// Counts of addresses, like `hosts` or `gap`, are strings since IPv6 ones
+/// don't fit the integers most JSON parsers support.
+pub const JSON_VERSION: u64 = 1;
+
+pub enum Value {
+    Null,
+    Bool(bool),
+    Number(u64),
+    String(String),
+    Array(Vec<Value>),
+    Object(Vec<(&'static str, Value)>),
+}
+
+impl Value {
+    pub fn string(value: impl fmt::Display) -> Self {
+        Value::String(value.to_string())
+    }
+
+    pub fn optional_string(value: Option<impl fmt::Display>) -> Self {
+        value.map(Value::string).unwrap_or(Value::Null)
+    }
+
+    fn write(&self, f: &mut fmt::Formatter<'_>, indent: usize) -> fmt::Result {
+        match self {
+            Value::Null => write!(f, "null"),
+            Value::Bool(value) => write!(f, "{}", value),
+            Value::Number(value) => write!(f, "{}", value),
+            Value::String(value) => write_string(f, value),
+            Value::Array(values) if values.is_empty() => write!(f, "[]"),
+            Value::Array(values) => {
+                writeln!(f, "[")?;
+                for (index, value) in values.iter().enumerate() {
+                    write!(f, "{:1$}", "", indent + 2)?;
+                    value.write(f, indent + 2)?;
+                    writeln!(f, "{}", if index + 1 < values.len() { "," } else { "" })?;
+                }
+                write!(f, "{:1$}]", "", indent)
+            }
+            Value::Object(fields) if fields.is_empty() => write!(f, "{{}}"),
+            Value::Object(fields) => {
+                writeln!(f, "{{")?;
+                for (index, (key, value)) in fields.iter().enumerate() {
+                    write!(f, "{:1$}", "", indent + 2)?;
+                    write_string(f, key)?;
+                    write!(f, ": ")?;
+                    value.write(f, indent + 2)?;
+                    writeln!(f, "{}", if index + 1 < fields.len() { "," } else { "" })?;
+                }
+                write!(f, "{:1$}}}", "", indent)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, 0)
+    }
+}
+
+/// Value displayed at the indentation of the field holding it.
+struct Indented<'a>(&'a Value, usize);
+
+impl fmt::Display for Indented<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.0.write(f, self.1)
+    }
+}
+
+struct Key<'a>(&'a str);
+
+impl fmt::Display for Key<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write_string(f, self.0)
+    }
+}
+
+/// Top level document, written field by field: arrays are streamed to the
+/// output as their elements are produced instead of being collected first.
+pub struct Document<'a, W: std::io::Write> {
+    out: &'a mut W,
+}
+
+impl<'a, W: std::io::Write> Document<'a, W> {
+    /// Opens the document with its schema version.
+    pub fn new(out: &'a mut W) -> Self {
+        write!(out, "{{\n  \"version\": {}", JSON_VERSION).unwrap();
+
+        Document { out }
+    }
+
+    pub fn field(&mut self, key: &str, value: Value) -> &mut Self {
+        write!(self.out, ",\n  {}: {}", Key(key), Indented(&value, 2)).unwrap();
+        self
+    }
+
+    pub fn array(&mut self, key: &str, values: impl Iterator<Item = Value>) -> &mut Self {
+        write!(self.out, ",\n  {}: [", Key(key)).unwrap();
+
+        let mut empty = true;
+        for value in values {
+            let separator = if empty { "" } else { "," };
+            write!(self.out, "{}\n    {}", separator, Indented(&value, 4)).unwrap();
+            empty = false;
+        }
+
+        if empty {
+            write!(self.out, "]").unwrap();
+        } else {
+            write!(self.out, "\n  ]").unwrap();
+        }
+        self
+    }
+
+    pub fn end(&mut self) {
+        writeln!(self.out, "\n}}").unwrap();
+    }
+}
+
+fn write_string(f: &mut fmt::Formatter<'_>, value: &str) -> fmt::Result {
+    write!(f, "\"")?;
+    for c in value.chars() {
+        match c {
+            '"' => write!(f, "\\\"")?,
+            '\\' => write!(f, "\\\\")?,
+            '\n' => write!(f, "\\n")?,
+            c if c.is_control() => write!(f, "\\u{:04x}", c as u32)?,
+            c => write!(f, "{}", c)?,
+        }
+    }
+    write!(f, "\"")
+}
+
+/// Fields describing a CIDR, shared by every subcommand.
+pub fn cidr<A: AddressFamily>(cidr: &CIDR<A>) -> Value {
+    Value::Object(vec![
+        ("address", Value::string(cidr.ip())),
+        ("network", Value::string(cidr.network_address())),
+        ("prefix", Value::Number(cidr.mask().prefix_length() as u64)),
+        ("mask", Value::string(cidr.mask())),
+        ("wildcard", Value::string(cidr.wildcard_mask())),
+        ("first", Value::optional_string(cidr.first_address())),
+        ("last", Value::optional_string(cidr.last_address())),
+        (
+            "broadcast",
+            Value::optional_string(cidr.broadcast_address()),
+        ),
+        ("hosts", Value::string(cidr.hosts())),
+        ("class", Value::optional_string(cidr.class())),
+        ("kind", Value::string(cidr.kind())),
+    ])
+}
+
+pub fn comparison(comparison: &CIDRComparison) -> Value {
+    let relation = match comparison {
+        CIDRComparison::Subset => "subset",
+        CIDRComparison::Superset => "superset",
+        CIDRComparison::Equals => "equals",
+        CIDRComparison::Different => "different",
+    };
+
+    Value::string(relation)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Document, Value};
+
+    #[test]
+    fn document_nested() {
+        let mut output = Vec::new();
+        Document::new(&mut output)
+            .array("empty", std::iter::empty())
+            .array(
+                "values",
+                [Value::Null, Value::Bool(true), Value::Number(42)].into_iter(),
+            )
+            .field(
+                "nested",
+                Value::Object(vec![
+                    ("key", Value::string("value")),
+                    ("array", Value::Array(vec![Value::Bool(false)])),
+                ]),
+            )
+            .end();
+
+        assert_eq!(
+            r#"{
+  "version": 1,
+  "empty": [],
+  "values": [
+    null,
+    true,
+    42
+  ],
+  "nested": {
+    "key": "value",
+    "array": [
+      false
+    ]
+  }
+}
+"#,
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn display_escaped_string() {
+        assert_eq!(
+            r#""a \"quoted\" \\ line\n\u0009""#,
+            Value::string("a \"quoted\" \\ line\n\t").to_string()
+        );
+    }
+
+    #[test]
+    fn escaped_strings_parse_as_json() {
+        let label = "a \"quoted\", back\\slashed\r\n\tlabel \u{0}\u{1f}\u{7f} é";
+        let mut output = Vec::new();
+        Document::new(&mut output)
+            .field(label, Value::string(label))
+            .array(
+                "labels",
+                [Value::Object(vec![("name", Value::string(label))])].into_iter(),
+            )
+            .end();
+
+        let document: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(label, document[label]);
+        assert_eq!(label, document["labels"][0]["name"]);
+    }
+
+    #[test]
+    fn goldens_parse_as_json() {
+        let testdata = concat!(env!("CARGO_MANIFEST_DIR"), "/src/cli/testdata");
+        let mut goldens = 0;
+        for entry in std::fs::read_dir(testdata).unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().and_then(|extension| extension.to_str()) != Some("json") {
+                continue;
+            }
+
+            let document: serde_json::Value =
+                serde_json::from_str(&std::fs::read_to_string(&path).unwrap())
+                    .unwrap_or_else(|err| panic!("{}: {}", path.display(), err));
+            assert_eq!(
+                super::JSON_VERSION,
+                document["version"],
+                "{}",
+                path.display()
+            );
+            goldens += 1;
+        }
+
+        assert!(goldens > 0);
+    }
+}
@@ -0,0 +1,257 @@
+use crate::cli::json::{Document, Value};
+use crate::cli::template::{self, Template};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6, PrefixTrie, CIDR};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub all: bool,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            all: false,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    /// The table lists one CIDR per line, optionally followed by a label.
+    /// Blank lines and lines starting with `#` are ignored.
+    pub fn execute(&mut self, raw_table: String, raw_ips: Vec<String>) -> Result<(), ErrorKind> {
+        let mut ipv4_table = PrefixTrie::<String, IPv4>::new();
+        let mut ipv6_table = PrefixTrie::<String, IPv6>::new();
+
+        for (index, line) in raw_table.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let (raw_cidr, label) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+            let in_line = |err| match err {
+                ErrorKind::InvalidInput(message) => {
+                    ErrorKind::InvalidInput(format!("table line {}: {}", index + 1, message))
+                }
+                err => err,
+            };
+
+            if arg_parser::is_ipv6(raw_cidr) {
+                let cidr = arg_parser::parse_cidr::<IPv6>("CIDR", raw_cidr.to_string())
+                    .map_err(in_line)?;
+                ipv6_table.insert(cidr, label.trim().to_string());
+            } else {
+                let cidr = arg_parser::parse_cidr::<IPv4>("CIDR", raw_cidr.to_string())
+                    .map_err(in_line)?;
+                ipv4_table.insert(cidr, label.trim().to_string());
+            }
+        }
+
+        if self.output == OutputFormat::Json {
+            // The addresses come from the command line, their lookups are
+            // collected so that an invalid one fails before any output.
+            let lookups = raw_ips
+                .into_iter()
+                .map(|raw_ip| {
+                    if arg_parser::is_ipv6(&raw_ip) {
+                        self.lookup_json(&ipv6_table, raw_ip)
+                    } else {
+                        self.lookup_json(&ipv4_table, raw_ip)
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            Document::new(&mut self.out)
+                .array("lookups", lookups.into_iter())
+                .end();
+            return Ok(());
+        }
+
+        let template = template::parse(self.format.as_deref())?;
+        for raw_ip in raw_ips {
+            if arg_parser::is_ipv6(&raw_ip) {
+                self.lookup(&ipv6_table, raw_ip, template.as_ref())?;
+            } else {
+                self.lookup(&ipv4_table, raw_ip, template.as_ref())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn matches<'a, A: AddressFamily>(
+        &self,
+        table: &'a PrefixTrie<String, A>,
+        ip: &A,
+    ) -> Vec<(CIDR<A>, &'a String)> {
+        if self.all {
+            table.matches(ip).collect()
+        } else {
+            table.longest_match(ip).into_iter().collect()
+        }
+    }
+
+    fn lookup_json<A: AddressFamily>(
+        &self,
+        table: &PrefixTrie<String, A>,
+        raw_ip: String,
+    ) -> Result<Value, ErrorKind> {
+        let ip = arg_parser::parse_ip::<A>("IP", raw_ip)?;
+        let matches = self.matches(table, &ip).into_iter().map(|(cidr, label)| {
+            Value::Object(vec![
+                ("cidr", Value::string(cidr)),
+                ("label", Value::string(label)),
+            ])
+        });
+
+        Ok(Value::Object(vec![
+            ("ip", Value::string(ip)),
+            ("matches", Value::Array(matches.collect())),
+        ]))
+    }
+
+    fn lookup<A: AddressFamily>(
+        &mut self,
+        table: &PrefixTrie<String, A>,
+        raw_ip: String,
+        template: Option<&Template>,
+    ) -> Result<(), ErrorKind> {
+        let ip = arg_parser::parse_ip::<A>("IP", raw_ip)?;
+
+        let matches = self.matches(table, &ip);
+        if template.is_some() {
+            for (cidr, _) in matches {
+                template::write_cidr(&mut self.out, template, &cidr);
+            }
+            return Ok(());
+        }
+
+        if matches.is_empty() {
+            writeln!(self.out, "{} no match", ip).unwrap();
+        }
+        for (cidr, label) in matches {
+            let line = format!("{} {} {}", ip, cidr, label);
+            writeln!(self.out, "{}", line.trim_end()).unwrap();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    static TABLE: &str = include_str!("testdata/lookup-table.txt");
+
+    fn ips(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|ip| ip.to_string()).collect()
+    }
+
+    #[test]
+    fn lookup_invalid_table() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "10.0.0.0/8 corporate\n\n10.1.0.0/33 datacenter".to_string(),
+            ips(&["10.1.0.1"]),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "table line 3: masklength must be between 0 and 32".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn lookup_invalid_ip() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(TABLE.to_string(), ips(&["10.1.0"]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 address format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn lookup_longest_match() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(
+            TABLE.to_string(),
+            ips(&[
+                "10.1.42.7",
+                "10.1.42.8",
+                "10.200.0.1",
+                "8.8.8.8",
+                "2001:db8:1::1",
+                "::1",
+            ]),
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/lookup.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn lookup_all_matches() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.all = true;
+        cli.execute(TABLE.to_string(), ips(&["10.1.42.7", "8.8.8.8"]))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/lookup-all.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn lookup_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.all = true;
+        cli.output = OutputFormat::Json;
+        cli.execute(TABLE.to_string(), ips(&["10.1.42.7", "8.8.8.8"]))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/lookup-all.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn lookup_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.all = true;
+        cli.format = Some("{cidr} {hosts}".to_string());
+        cli.execute(TABLE.to_string(), ips(&["10.1.42.7", "8.8.8.8"]))
+            .unwrap();
+
+        assert_eq!(
+            "10.0.0.0/8 16777214
+10.1.0.0/16 65534
+10.1.42.0/24 254
+10.1.42.7/32 1
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
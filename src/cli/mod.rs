@@ -2,8 +2,25 @@ pub mod aggregate;
 mod arg_parser;
 mod cidr_formatter;
 pub mod compare;
+pub mod compat;
 pub mod describe;
 mod errors;
+pub mod exclude;
+pub mod free;
+pub mod hosts;
+mod json;
+pub mod lookup;
+mod output;
+pub mod overlaps;
+pub mod plan;
+pub mod range;
+pub mod set;
 pub mod split;
+pub mod summarize;
+mod table;
+mod template;
+pub mod wildcard;
+mod wildcard_formatter;
 
 pub use self::errors::ErrorKind;
+pub use self::output::OutputFormat;
@@ -0,0 +1,21 @@
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    /// Pretty printed JSON object, see `JSON_VERSION` for its schema.
+    Json,
+    /// One row per network, with a header row.
+    Csv,
+    Tsv,
+    /// Aligned columns for humans.
+    Table,
+}
+
+impl OutputFormat {
+    pub fn is_tabular(&self) -> bool {
+        matches!(
+            self,
+            OutputFormat::Csv | OutputFormat::Tsv | OutputFormat::Table
+        )
+    }
+}
@@ -0,0 +1,302 @@
+use crate::cli::compare::describe_comparison;
+use crate::cli::json::{self, Document, Value};
+use crate::cli::template::{self, Template};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{overlapping_pairs, AddressFamily, IPRange, IPv4, IPv6, CIDR};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+struct Entry<A: AddressFamily> {
+    label: String,
+    cidr: CIDR<A>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    /// Each entry is either `name,cidr`, further CSV columns being ignored,
+    /// or a CIDR optionally followed by a label. Blank entries, ones starting
+    /// with `#` and `name,cidr` headers, wherever their file starts among the
+    /// entries, are ignored. Fails once every overlapping pair has been
+    /// displayed.
+    pub fn execute(&mut self, raw_entries: Vec<String>) -> Result<(), ErrorKind> {
+        let template = template::parse(self.format.as_deref())?;
+        let mut ipv4_entries = Vec::new();
+        let mut ipv6_entries = Vec::new();
+
+        for (index, line) in raw_entries.iter().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let mut fields = line.splitn(3, ',');
+            let (label, raw_cidr) = match (fields.next(), fields.next()) {
+                (Some(label), Some(raw_cidr)) => (label.trim(), raw_cidr.trim()),
+                _ => {
+                    let (raw_cidr, label) =
+                        line.split_once(char::is_whitespace).unwrap_or((line, ""));
+                    (label.trim(), raw_cidr)
+                }
+            };
+            if raw_cidr.eq_ignore_ascii_case("cidr") {
+                continue;
+            }
+
+            let in_line = |err| match err {
+                ErrorKind::InvalidInput(message) => {
+                    ErrorKind::InvalidInput(format!("line {}: {}", index + 1, message))
+                }
+                err => err,
+            };
+
+            if arg_parser::is_ipv6(raw_cidr) {
+                let cidr = arg_parser::parse_cidr::<IPv6>("CIDR", raw_cidr.to_string())
+                    .map_err(in_line)?;
+                ipv6_entries.push(Entry {
+                    label: label.to_string(),
+                    cidr,
+                });
+            } else {
+                let cidr = arg_parser::parse_cidr::<IPv4>("CIDR", raw_cidr.to_string())
+                    .map_err(in_line)?;
+                ipv4_entries.push(Entry {
+                    label: label.to_string(),
+                    cidr,
+                });
+            }
+        }
+
+        if ipv4_entries.is_empty() && ipv6_entries.is_empty() {
+            return Err(ErrorKind::InvalidInput(
+                "expecting at least one CIDR".to_string(),
+            ));
+        }
+
+        let conflicts = if self.output == OutputFormat::Json {
+            let ipv4_pairs = overlapping_pairs(&cidrs(&ipv4_entries));
+            let ipv6_pairs = overlapping_pairs(&cidrs(&ipv6_entries));
+            Document::new(&mut self.out)
+                .array(
+                    "overlaps",
+                    ipv4_pairs
+                        .iter()
+                        .map(|pair| pair_json(&ipv4_entries, pair))
+                        .chain(ipv6_pairs.iter().map(|pair| pair_json(&ipv6_entries, pair))),
+                )
+                .end();
+            ipv4_pairs.len() + ipv6_pairs.len()
+        } else {
+            self.report(&ipv4_entries, template.as_ref())
+                + self.report(&ipv6_entries, template.as_ref())
+        };
+        if conflicts > 0 {
+            return Err(ErrorKind::CheckFailed(format!(
+                "found {} overlapping pair{}",
+                conflicts,
+                if conflicts > 1 { "s" } else { "" }
+            )));
+        }
+
+        if self.output != OutputFormat::Json && template.is_none() {
+            writeln!(self.out, "no overlap found").unwrap();
+        }
+
+        Ok(())
+    }
+
+    /// Describes each overlapping pair, or renders their overlap with the
+    /// template.
+    fn report<A: AddressFamily>(
+        &mut self,
+        entries: &[Entry<A>],
+        template: Option<&Template>,
+    ) -> usize {
+        let pairs = overlapping_pairs(&cidrs(entries));
+
+        for (index, other) in &pairs {
+            let (entry, other) = (&entries[*index], &entries[*other]);
+            let overlap = entry.cidr.intersection(&other.cidr).unwrap();
+            if template.is_some() {
+                template::write_cidr(&mut self.out, template, &overlap);
+                continue;
+            }
+
+            writeln!(
+                self.out,
+                "{} {} {}, overlapping {}",
+                Self::name(entry),
+                describe_comparison(&entry.cidr.compare(&other.cidr)),
+                Self::name(other),
+                IPRange::from(overlap)
+            )
+            .unwrap();
+        }
+
+        pairs.len()
+    }
+
+    fn name<A: AddressFamily>(entry: &Entry<A>) -> String {
+        if entry.label.is_empty() {
+            return entry.cidr.to_string();
+        }
+
+        format!("{} ({})", entry.label, entry.cidr)
+    }
+}
+
+fn cidrs<A: AddressFamily>(entries: &[Entry<A>]) -> Vec<CIDR<A>> {
+    entries.iter().map(|entry| entry.cidr).collect()
+}
+
+fn pair_json<A: AddressFamily>(entries: &[Entry<A>], (index, other): &(usize, usize)) -> Value {
+    let (entry, other) = (&entries[*index], &entries[*other]);
+    let overlap = entry.cidr.intersection(&other.cidr).unwrap();
+    let label = |entry: &Entry<A>| {
+        Value::optional_string(Some(&entry.label).filter(|label| !label.is_empty()))
+    };
+
+    Value::Object(vec![
+        ("cidr", Value::string(entry.cidr)),
+        ("label", label(entry)),
+        ("other", Value::string(other.cidr)),
+        ("other_label", label(other)),
+        (
+            "relation",
+            json::comparison(&entry.cidr.compare(&other.cidr)),
+        ),
+        ("overlap", Value::string(IPRange::from(overlap))),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    fn entries(raw: &str) -> Vec<String> {
+        raw.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn overlaps_no_entry() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(entries("name,cidr\n# nothing yet\n"));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting at least one CIDR".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn overlaps_invalid_entry() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(entries("vpc-a,10.0.0.0/16\nvpc-b,10.1.0.0/33"));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "line 2: masklength must be between 0 and 32".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn overlaps_none() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(entries(
+            "10.0.0.0/16 vpc-a\n10.1.0.0/16 vpc-b\n2001:db8::/32",
+        ))
+        .unwrap();
+
+        assert_eq!("no overlap found\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn overlaps_header_after_arguments() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let mut raw_entries = vec!["192.168.0.0/24 lab".to_string()];
+        raw_entries.extend(entries(
+            "# exported\nname,cidr,owner\nvpc-a,10.0.0.0/16,network team\nvpc-b,10.1.0.0/16,",
+        ));
+        cli.execute(raw_entries).unwrap();
+
+        assert_eq!("no overlap found\n", String::from_utf8(output).unwrap());
+    }
+
+    #[test]
+    fn overlaps_conflicts() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let result = cli.execute(entries(include_str!("testdata/overlaps-input.csv")));
+
+        assert_eq!(
+            Err(ErrorKind::CheckFailed(
+                "found 4 overlapping pairs".to_string()
+            )),
+            result
+        );
+
+        let expected_output = include_str!("testdata/overlaps.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn overlaps_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        let result = cli.execute(entries(include_str!("testdata/overlaps-input.csv")));
+
+        assert_eq!(
+            Err(ErrorKind::CheckFailed(
+                "found 4 overlapping pairs".to_string()
+            )),
+            result
+        );
+
+        let expected_output = include_str!("testdata/overlaps.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn overlaps_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{cidr}".to_string());
+        let result = cli.execute(entries(include_str!("testdata/overlaps-input.csv")));
+
+        assert_eq!(
+            Err(ErrorKind::CheckFailed(
+                "found 4 overlapping pairs".to_string()
+            )),
+            result
+        );
+        assert_eq!(
+            "10.0.4.0/22\n10.0.6.0/24\n10.1.0.0/16\n10.0.6.0/24\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
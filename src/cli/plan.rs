@@ -0,0 +1,358 @@
+use crate::cli::cidr_formatter::column_width;
+use crate::cli::json::{self, Document, Value};
+use crate::cli::template;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{self, AddressFamily, IPv4, IPv6, PlanningError};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    /// Requirements are formatted as `NAME:HOSTS` or `NAME:COUNTxHOSTS`,
+    /// blank ones and ones starting with `#` are ignored.
+    pub fn execute(
+        &mut self,
+        raw_supernet: String,
+        raw_requirements: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_supernet) {
+            self.plan::<IPv6>(raw_supernet, raw_requirements)
+        } else {
+            self.plan::<IPv4>(raw_supernet, raw_requirements)
+        }
+    }
+
+    fn plan<A: AddressFamily>(
+        &mut self,
+        raw_supernet: String,
+        raw_requirements: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        let supernet = arg_parser::parse_cidr::<A>("SUPERNET", raw_supernet)?;
+        let template = template::parse(self.format.as_deref())?;
+
+        let mut budget = arg_parser::RequirementBudget::new(&supernet);
+        let mut requirements = Vec::new();
+        for raw_requirement in raw_requirements {
+            let raw_requirement = raw_requirement.trim();
+            if raw_requirement.is_empty() || raw_requirement.starts_with('#') {
+                continue;
+            }
+
+            requirements.extend(arg_parser::parse_requirement(
+                raw_requirement,
+                &supernet,
+                &mut budget,
+            )?);
+        }
+        if requirements.is_empty() {
+            return Err(ErrorKind::InvalidInput(
+                "expecting at least one requirement".to_string(),
+            ));
+        }
+
+        let plan = net::plan(&supernet, &requirements).map_err(|err| match err {
+            PlanningError::DoesNotFit(name) => ErrorKind::InvalidInput(format!(
+                "cannot allocate {} in {}: not enough free space",
+                name,
+                supernet.network_address()
+            )),
+        })?;
+
+        if self.output == OutputFormat::Json {
+            let allocations = plan.allocations.iter().map(|allocation| {
+                Value::Object(vec![
+                    ("name", Value::string(&allocation.name)),
+                    ("required", Value::string(allocation.hosts)),
+                    ("cidr", json::cidr(&allocation.cidr)),
+                ])
+            });
+            Document::new(&mut self.out)
+                .field("supernet", json::cidr(&supernet))
+                .array("allocations", allocations)
+                .array("free", plan.free.cidrs().map(|cidr| json::cidr(&cidr)))
+                .end();
+            return Ok(());
+        }
+
+        if template.is_some() {
+            for allocation in &plan.allocations {
+                template::write_cidr(&mut self.out, template.as_ref(), &allocation.cidr);
+            }
+            return Ok(());
+        }
+
+        let width = column_width::<A>();
+        let name_width = plan
+            .allocations
+            .iter()
+            .map(|allocation| allocation.name.len() + 1)
+            .max()
+            .unwrap_or(0)
+            .max(11);
+
+        writeln!(
+            self.out,
+            "{:name_width$}{}",
+            "Network:",
+            supernet.network_address()
+        )
+        .unwrap();
+        writeln!(self.out, "=>").unwrap();
+
+        let ranges: Vec<String> = plan
+            .allocations
+            .iter()
+            .map(|allocation| {
+                format!(
+                    "{} - {}",
+                    allocation.cidr.first_address().unwrap(),
+                    allocation.cidr.last_address().unwrap()
+                )
+            })
+            .collect();
+        let range_width = ranges
+            .iter()
+            .map(|range| range.len() + 1)
+            .max()
+            .unwrap_or(0);
+
+        for (allocation, range) in plan.allocations.iter().zip(ranges) {
+            writeln!(
+                self.out,
+                "{:name_width$}{:width$}{:range_width$}{} hosts ({} required)",
+                allocation.name,
+                format!("{}", allocation.cidr),
+                range,
+                allocation.cidr.hosts(),
+                allocation.hosts,
+            )
+            .unwrap();
+        }
+
+        writeln!(self.out, "=>").unwrap();
+        if plan.free.is_empty() {
+            writeln!(self.out, "{:name_width$}none", "Free:").unwrap();
+        }
+        for cidr in plan.free.cidrs() {
+            writeln!(self.out, "{:name_width$}{}", "Free:", cidr).unwrap();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    fn requirements(raw: &[&str]) -> Vec<String> {
+        raw.iter()
+            .map(|requirement| requirement.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn plan_empty_supernet() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("".to_string(), requirements(&["users:500"]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty SUPERNET argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_no_requirement() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.0/22".to_string(), requirements(&["# comment", ""]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting at least one requirement".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_invalid_requirement() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.0/22".to_string(), requirements(&["users=500"]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid requirement users=500, expecting NAME:HOSTS or NAME:COUNTxHOSTS"
+                    .to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_does_not_fit() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "10.0.0.0/24".to_string(),
+            requirements(&["users:200", "voip:100"]),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "cannot allocate voip in 10.0.0.0/24: not enough free space".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_site() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(
+            "10.0.0.0/22".to_string(),
+            requirements(&["users:500", "voip: 200", "mgmt:30", "p2p links:4x2"]),
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/plan.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn plan_count_exceeds_supernet() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.0/24".to_string(), requirements(&["hosts:300x1"]));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "cannot allocate hosts in 10.0.0.0/24: not enough free space".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_requirements_exceed_supernet() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "10.0.0.0/24".to_string(),
+            requirements(&["users:100", "voip:60", "p2p links:40x2"]),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "cannot allocate p2p links in 10.0.0.0/24: not enough free space".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_too_many_subnets() {
+        for supernet in ["0.0.0.0/0", "2001:db8::/32"] {
+            let mut output = Vec::new();
+            let mut cli = super::CLI::new(&mut output);
+            let output = cli.execute(
+                supernet.to_string(),
+                requirements(&["users:100", "hosts:4000000000x1"]),
+            );
+
+            assert_eq!(
+                Err(ErrorKind::InvalidInput(
+                    "cannot allocate hosts: plans are limited to 65536 subnets".to_string()
+                )),
+                output
+            );
+        }
+
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "0.0.0.0/0".to_string(),
+            requirements(&["links:65535x2", "hosts:2x1"]),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "cannot allocate hosts: plans are limited to 65536 subnets".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn plan_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute(
+            "10.0.0.0/24".to_string(),
+            requirements(&["users:50", "p2p links:2x2"]),
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/plan.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn plan_json_escapes_names() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute(
+            "10.0.0.0/24".to_string(),
+            requirements(&["\"core\" \\ links\t:2"]),
+        )
+        .unwrap();
+
+        let document: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!("\"core\" \\ links", document["allocations"][0]["name"]);
+    }
+
+    #[test]
+    fn plan_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{cidr} {first}-{last}".to_string());
+        cli.execute(
+            "10.0.0.0/24".to_string(),
+            requirements(&["users:50", "p2p links:2x2"]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "10.0.0.0/26 10.0.0.1-10.0.0.62
+10.0.0.64/31 10.0.0.64-10.0.0.65
+10.0.0.66/31 10.0.0.66-10.0.0.67
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
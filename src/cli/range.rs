@@ -0,0 +1,183 @@
+use crate::cli::json::{self, Document, Value};
+use crate::cli::template;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPRange, IPv4, IPv6};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    pub fn execute(&mut self, raw: String) -> Result<(), ErrorKind> {
+        match (arg_parser::is_ipv6(&raw), raw.contains('-')) {
+            (true, true) => self.decompose::<IPv6>(raw),
+            (false, true) => self.decompose::<IPv4>(raw),
+            (true, false) => self.expand::<IPv6>(raw),
+            (false, false) => self.expand::<IPv4>(raw),
+        }
+    }
+
+    fn decompose<A: AddressFamily>(&mut self, raw_range: String) -> Result<(), ErrorKind> {
+        let range = arg_parser::parse_range::<A>("RANGE", raw_range)?;
+        let template = template::parse(self.format.as_deref())?;
+        if self.output == OutputFormat::Json {
+            self.write_json(&range);
+            return Ok(());
+        }
+
+        for cidr in range.cidrs() {
+            template::write_cidr(&mut self.out, template.as_ref(), &cidr);
+        }
+
+        Ok(())
+    }
+
+    fn expand<A: AddressFamily>(&mut self, raw_cidr: String) -> Result<(), ErrorKind> {
+        let cidr = arg_parser::parse_cidr::<A>("RANGE", raw_cidr)?;
+        let template = template::parse(self.format.as_deref())?;
+        if self.output == OutputFormat::Json {
+            self.write_json(&IPRange::from(cidr));
+            return Ok(());
+        }
+
+        if template.is_some() {
+            template::write_cidr(&mut self.out, template.as_ref(), &cidr);
+            return Ok(());
+        }
+
+        writeln!(self.out, "{}", IPRange::from(cidr)).unwrap();
+
+        Ok(())
+    }
+
+    fn write_json<A: AddressFamily>(&mut self, range: &IPRange<A>) {
+        Document::new(&mut self.out)
+            .field("range", Value::string(range))
+            .field("first", Value::string(range.first()))
+            .field("last", Value::string(range.last()))
+            .field("addresses", Value::string(range.addresses()))
+            .array("cidrs", range.cidrs().map(|cidr| json::cidr(&cidr)))
+            .end();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    #[test]
+    fn range_empty() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty RANGE argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn range_invalid_address() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.5-10.0.1".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 address format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn range_reversed_bounds() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.1.17 - 10.0.0.5".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "range first address must not be after its last address".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn range_to_cidrs() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.0.0.5-10.0.1.17".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/range-to-cidrs.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn range_ipv6_to_cidrs() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("2001:db8::ffff - 2001:db8::1:1".to_string())
+            .unwrap();
+
+        let expected_output = "2001:db8::ffff/128\n2001:db8::1:0/127\n";
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn cidr_to_range() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.0.10.15/23".to_string()).unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!("10.0.10.0-10.0.11.255\n", actual_output);
+    }
+
+    #[test]
+    fn range_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("10.0.0.5-10.0.0.9".to_string()).unwrap();
+
+        let expected_output = include_str!("testdata/range.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn range_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{cidr} ({hosts})".to_string());
+        cli.execute("10.0.0.5-10.0.0.9".to_string()).unwrap();
+
+        assert_eq!(
+            "10.0.0.5/32 (1)\n10.0.0.6/31 (2)\n10.0.0.8/31 (2)\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+}
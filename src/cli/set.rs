@@ -0,0 +1,218 @@
+use crate::cli::json::{self, Document, Value};
+use crate::cli::template::{self, Template};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPSet, IPv4, IPv6};
+
+use super::arg_parser;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Operation {
+    Union,
+    Intersect,
+    Subtract,
+}
+
+impl Operation {
+    fn name(&self) -> &'static str {
+        match self {
+            Operation::Union => "union",
+            Operation::Intersect => "intersect",
+            Operation::Subtract => "subtract",
+        }
+    }
+}
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    /// Each set is a comma separated list of CIDRs and ranges. Subtract
+    /// removes every other set from the first one.
+    pub fn execute(
+        &mut self,
+        operation: Operation,
+        raw_sets: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        if raw_sets.len() < 2 {
+            return Err(ErrorKind::InvalidInput(
+                "expecting at least two SET arguments".to_string(),
+            ));
+        }
+
+        if arg_parser::is_ipv6(&raw_sets[0]) {
+            self.execute_operation::<IPv6>(operation, raw_sets)
+        } else {
+            self.execute_operation::<IPv4>(operation, raw_sets)
+        }
+    }
+
+    fn execute_operation<A: AddressFamily>(
+        &mut self,
+        operation: Operation,
+        raw_sets: Vec<String>,
+    ) -> Result<(), ErrorKind> {
+        let template = template::parse(self.format.as_deref())?;
+        let mut sets = raw_sets
+            .into_iter()
+            .map(arg_parser::parse_set::<A>)
+            .collect::<Result<Vec<_>, _>>()?
+            .into_iter();
+
+        let first = sets.next().unwrap_or_default();
+        let result = sets.fold(first, |result, set| match operation {
+            Operation::Union => result.union(&set),
+            Operation::Intersect => result.intersection(&set),
+            Operation::Subtract => result.difference(&set),
+        });
+
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("operation", Value::string(operation.name()))
+                .field("addresses", Value::string(result.addresses()))
+                .array("cidrs", result.cidrs().map(|cidr| json::cidr(&cidr)))
+                .end();
+            return Ok(());
+        }
+
+        self.print(&result, template.as_ref());
+
+        Ok(())
+    }
+
+    fn print<A: AddressFamily>(&mut self, set: &IPSet<A>, template: Option<&Template>) {
+        for cidr in set.cidrs() {
+            template::write_cidr(&mut self.out, template, &cidr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Operation;
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    fn execute(operation: Operation, raw_sets: &[&str]) -> Result<String, ErrorKind> {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(
+            operation,
+            raw_sets.iter().map(|set| set.to_string()).collect(),
+        )?;
+
+        Ok(String::from_utf8(output).unwrap())
+    }
+
+    #[test]
+    fn set_single_argument() {
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting at least two SET arguments".to_string()
+            )),
+            execute(Operation::Union, &["10.0.0.0/8"])
+        );
+    }
+
+    #[test]
+    fn set_empty_argument() {
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty SET argument".to_string()
+            )),
+            execute(Operation::Union, &["10.0.0.0/8", ""])
+        );
+    }
+
+    #[test]
+    fn set_invalid_entry() {
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 CIDR format".to_string()
+            )),
+            execute(
+                Operation::Union,
+                &["10.0.0.0/8", "10.0.0.0/8,2001:db8::/32"]
+            )
+        );
+    }
+
+    #[test]
+    fn set_union() {
+        assert_eq!(
+            Ok("10.0.0.0/23\n10.0.4.0/24\n192.168.0.0/16\n".to_string()),
+            execute(
+                Operation::Union,
+                &["10.0.0.0/24,10.0.4.0/24", "10.0.1.0/24", "192.168.0.0/16"]
+            )
+        );
+    }
+
+    #[test]
+    fn set_intersect() {
+        assert_eq!(
+            Ok("10.0.42.0/24\n".to_string()),
+            execute(
+                Operation::Intersect,
+                &["10.0.0.0/16, 192.168.0.0/24", "10.0.42.0/24,172.16.0.0/12"]
+            )
+        );
+        assert_eq!(
+            Ok("".to_string()),
+            execute(Operation::Intersect, &["10.0.0.0/16", "10.1.0.0/16"])
+        );
+    }
+
+    #[test]
+    fn set_subtract() {
+        let expected_output = include_str!("testdata/set-subtract.golden");
+
+        assert_eq!(
+            Ok(expected_output.to_string()),
+            execute(
+                Operation::Subtract,
+                &["10.0.0.0/22", "10.0.1.0/24", "10.0.3.10 - 10.0.3.20"]
+            )
+        );
+    }
+
+    #[test]
+    fn set_ipv6() {
+        assert_eq!(
+            Ok("2001:db8::/33\n".to_string()),
+            execute(
+                Operation::Subtract,
+                &["2001:db8::/32", "2001:db8:8000::/33"]
+            )
+        );
+    }
+
+    #[test]
+    fn set_union_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute(
+            Operation::Union,
+            vec![
+                "10.0.0.0/25".to_string(),
+                "10.0.0.128/25,10.0.1.0/24".to_string(),
+            ],
+        )
+        .unwrap();
+
+        let expected_output = include_str!("testdata/set-union.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+}
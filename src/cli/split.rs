@@ -1,12 +1,27 @@
 use crate::cli::cidr_formatter::CIDRFormatter;
-use crate::cli::ErrorKind;
-use crate::net::CIDR;
+use crate::cli::json::{self, Document};
+use crate::cli::{table, template};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6, Mask, CIDR};
 
 use super::arg_parser;
 
+/// Size of the networks to split the CIDR in.
+pub enum NewMask {
+    Mask(String),
+    /// Smallest networks providing at least this number of hosts.
+    Hosts(u128),
+    /// Largest networks to split the CIDR in at least this number of subnets.
+    Count(u128),
+}
+
 pub struct CLI<W: std::io::Write> {
     pub out: W,
     pub with_binary: bool,
+    pub output: OutputFormat,
+    /// Comma separated columns of the csv, tsv and table outputs.
+    pub columns: Option<String>,
+    pub format: Option<String>,
 }
 
 impl<W: std::io::Write> CLI<W> {
@@ -14,12 +29,81 @@ impl<W: std::io::Write> CLI<W> {
         CLI {
             out,
             with_binary: true,
+            output: OutputFormat::Text,
+            columns: None,
+            format: None,
+        }
+    }
+
+    pub fn execute(&mut self, raw_cidr: String, new_mask: NewMask) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_cidr) {
+            self.split::<IPv6>(raw_cidr, new_mask)
+        } else {
+            self.split::<IPv4>(raw_cidr, new_mask)
         }
     }
 
-    pub fn execute(&mut self, raw_cidr: String, raw_new_mask: String) -> Result<(), ErrorKind> {
-        let cidr = arg_parser::parse_cidr("CIDR", raw_cidr)?;
-        let new_mask = arg_parser::parse_mask("MASK", raw_new_mask)?;
+    fn split<A: AddressFamily>(
+        &mut self,
+        raw_cidr: String,
+        new_mask: NewMask,
+    ) -> Result<(), ErrorKind> {
+        let cidr = arg_parser::parse_cidr::<A>("CIDR", raw_cidr)?;
+        let template = template::parse(self.format.as_deref())?;
+        let new_mask = match new_mask {
+            NewMask::Mask(raw_new_mask) => arg_parser::parse_mask::<A>("MASK", raw_new_mask)?,
+            NewMask::Hosts(hosts) => Mask::for_hosts(hosts)
+                .filter(|mask| *mask >= cidr.mask())
+                .ok_or_else(|| {
+                    ErrorKind::InvalidInput(format!(
+                        "{} is too small to hold {} hosts",
+                        cidr.network_address(),
+                        hosts
+                    ))
+                })?,
+            NewMask::Count(0) => {
+                return Err(ErrorKind::InvalidInput(
+                    "count must be greater than 0".to_string(),
+                ))
+            }
+            NewMask::Count(count) => Mask::for_subnets(&cidr.mask(), count).ok_or_else(|| {
+                ErrorKind::InvalidInput(format!(
+                    "{} cannot be split in {} subnets",
+                    cidr.network_address(),
+                    count
+                ))
+            })?,
+        };
+
+        let subnets: Box<dyn Iterator<Item = CIDR<A>>> = if cidr.is_network_address() {
+            Box::new(cidr.split(new_mask))
+        } else {
+            Box::new([CIDR::new(cidr.ip(), new_mask)].into_iter())
+        };
+
+        if self.output == OutputFormat::Json {
+            Document::new(&mut self.out)
+                .field("cidr", json::cidr(&cidr))
+                .array("subnets", subnets.map(|subnet| json::cidr(&subnet)))
+                .end();
+            return Ok(());
+        }
+
+        if template.is_some() {
+            for subnet in subnets {
+                template::write_cidr(&mut self.out, template.as_ref(), &subnet);
+            }
+            return Ok(());
+        }
+
+        if self.output.is_tabular() {
+            return table::write_cidrs(
+                &mut self.out,
+                self.output,
+                self.columns.as_deref(),
+                subnets,
+            );
+        }
 
         if !cidr.is_network_address() {
             write!(
@@ -56,13 +140,14 @@ impl<W: std::io::Write> CLI<W> {
 mod tests {
     use std::fs;
 
-    use crate::cli::ErrorKind;
+    use super::NewMask;
+    use crate::cli::{ErrorKind, OutputFormat};
 
     #[test]
     fn split_empty_cidr() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        let output = cli.execute("".to_string(), "24".to_string());
+        let output = cli.execute("".to_string(), NewMask::Mask("24".to_string()));
 
         assert_eq!(
             Err(ErrorKind::InvalidInput(
@@ -76,7 +161,7 @@ mod tests {
     fn split_empty_mask() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        let output = cli.execute("10.12.5.255/32".to_string(), "".to_string());
+        let output = cli.execute("10.12.5.255/32".to_string(), NewMask::Mask("".to_string()));
 
         assert_eq!(
             Err(ErrorKind::InvalidInput(
@@ -90,7 +175,7 @@ mod tests {
     fn split_unparsable_cidr() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        let output = cli.execute("not a CIDR".to_string(), "24".to_string());
+        let output = cli.execute("not a CIDR".to_string(), NewMask::Mask("24".to_string()));
 
         assert_eq!(
             Err(ErrorKind::InvalidInput(
@@ -104,7 +189,10 @@ mod tests {
     fn split_invalid_cidr_mask() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        let output = cli.execute("10.12.5.255/200".to_string(), "24".to_string());
+        let output = cli.execute(
+            "10.12.5.255/200".to_string(),
+            NewMask::Mask("24".to_string()),
+        );
 
         assert_eq!(
             Err(ErrorKind::InvalidInput(
@@ -118,7 +206,10 @@ mod tests {
     fn split_unparsable_mask() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        let output = cli.execute("10.12.5.255/32".to_string(), "not a mask".to_string());
+        let output = cli.execute(
+            "10.12.5.255/32".to_string(),
+            NewMask::Mask("not a mask".to_string()),
+        );
 
         assert_eq!(
             Err(ErrorKind::InvalidInput("invalid mask format".to_string())),
@@ -130,7 +221,10 @@ mod tests {
     fn split_invalid_mask_range() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        let output = cli.execute("10.12.5.255/32".to_string(), "200".to_string());
+        let output = cli.execute(
+            "10.12.5.255/32".to_string(),
+            NewMask::Mask("200".to_string()),
+        );
 
         assert_eq!(
             Err(ErrorKind::InvalidInput(
@@ -144,7 +238,7 @@ mod tests {
     fn split_host_slash_24_to_26() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        cli.execute("10.13.5.78/24".to_string(), "26".to_string())
+        cli.execute("10.13.5.78/24".to_string(), NewMask::Mask("26".to_string()))
             .unwrap();
 
         let expected_output = fs::read_to_string("src/cli/testdata/split-host.golden").unwrap();
@@ -158,7 +252,7 @@ mod tests {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
         cli.with_binary = false;
-        cli.execute("10.13.5.78/24".to_string(), "26".to_string())
+        cli.execute("10.13.5.78/24".to_string(), NewMask::Mask("26".to_string()))
             .unwrap();
 
         let expected_output =
@@ -172,7 +266,7 @@ mod tests {
     fn split_network_slash_24_to_26() {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
-        cli.execute("10.13.5.0/24".to_string(), "26".to_string())
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Mask("26".to_string()))
             .unwrap();
 
         let expected_output = fs::read_to_string("src/cli/testdata/split-network.golden").unwrap();
@@ -186,7 +280,7 @@ mod tests {
         let mut output = Vec::new();
         let mut cli = super::CLI::new(&mut output);
         cli.with_binary = false;
-        cli.execute("10.13.5.0/24".to_string(), "26".to_string())
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Mask("26".to_string()))
             .unwrap();
 
         let expected_output =
@@ -195,4 +289,242 @@ mod tests {
 
         assert_eq!(expected_output, actual_output);
     }
+
+    #[test]
+    fn split_invalid_ipv6_mask_range() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "2001:db8::/32".to_string(),
+            NewMask::Mask("130".to_string()),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "mask length must be between 0 and 128".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn split_ipv6_network_slash_47_to_48_no_binary() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.with_binary = false;
+        cli.execute("2001:db8::/47".to_string(), NewMask::Mask("48".to_string()))
+            .unwrap();
+
+        let expected_output =
+            fs::read_to_string("src/cli/testdata/split-network-ipv6-no-binary.golden").unwrap();
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_non_contiguous_mask() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(
+            "10.12.5.0/24".to_string(),
+            NewMask::Mask("255.0.255.0".to_string()),
+        );
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "mask must be contiguous but bit 17 breaks contiguity".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn split_network_slash_24_to_26_dotted_masks() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(
+            "10.13.5.0/255.255.255.0".to_string(),
+            NewMask::Mask("0.0.0.63".to_string()),
+        )
+        .unwrap();
+
+        let expected_output = fs::read_to_string("src/cli/testdata/split-network.golden").unwrap();
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_top_of_address_space() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.with_binary = false;
+        cli.execute(
+            "255.255.255.252/30".to_string(),
+            NewMask::Mask("32".to_string()),
+        )
+        .unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+        let networks: Vec<&str> = actual_output
+            .lines()
+            .filter(|line| line.starts_with("Network:"))
+            .map(|line| line.trim_end())
+            .collect();
+
+        assert_eq!(
+            vec![
+                "Network:   255.255.255.252/32",
+                "Network:   255.255.255.253/32",
+                "Network:   255.255.255.254/32",
+                "Network:   255.255.255.255/32",
+            ],
+            networks
+        );
+    }
+
+    #[test]
+    fn split_by_hosts() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Hosts(60))
+            .unwrap();
+
+        let expected_output = fs::read_to_string("src/cli/testdata/split-network.golden").unwrap();
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_by_hosts_too_many() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.13.5.0/24".to_string(), NewMask::Hosts(255));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "10.13.5.0/24 is too small to hold 255 hosts".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn split_by_count() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Count(3))
+            .unwrap();
+
+        let expected_output = fs::read_to_string("src/cli/testdata/split-network.golden").unwrap();
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_by_count_too_many() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.13.5.0/24".to_string(), NewMask::Count(257));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "10.13.5.0/24 cannot be split in 257 subnets".to_string()
+            )),
+            output
+        );
+
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.13.5.0/24".to_string(), NewMask::Count(0));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "count must be greater than 0".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn split_network_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Count(2))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/split-network.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_network_table() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Table;
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Mask("26".to_string()))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/split-network-table.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_network_csv_columns() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Csv;
+        cli.columns = Some("network,first,last".to_string());
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Mask("26".to_string()))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/split-network.csv");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn split_unknown_column() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Tsv;
+        cli.columns = Some("network,size".to_string());
+        let result = cli.execute("10.13.5.0/24".to_string(), NewMask::Mask("26".to_string()));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "unknown column size, expecting one of network, first, last, broadcast, hosts, kind"
+                    .to_string()
+            )),
+            result
+        );
+        assert!(output.is_empty());
+    }
+
+    #[test]
+    fn split_network_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{network} {netmask}".to_string());
+        cli.execute("10.13.5.0/24".to_string(), NewMask::Mask("26".to_string()))
+            .unwrap();
+
+        assert_eq!(
+            "10.13.5.0 255.255.255.192
+10.13.5.64 255.255.255.192
+10.13.5.128 255.255.255.192
+10.13.5.192 255.255.255.192
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
 }
@@ -0,0 +1,170 @@
+use crate::cli::json::{self, Document};
+use crate::cli::template::{self, Template};
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{self, AddressFamily, IPv4, IPv6, CIDR};
+
+use super::arg_parser;
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub max_extra_addresses: u128,
+    pub output: OutputFormat,
+    pub format: Option<String>,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            max_extra_addresses: 0,
+            output: OutputFormat::Text,
+            format: None,
+        }
+    }
+
+    /// Summarizes IPv4 and IPv6 CIDRs separately, blank lines are ignored.
+    pub fn execute(&mut self, raw_cidrs: Vec<String>) -> Result<(), ErrorKind> {
+        let template = template::parse(self.format.as_deref())?;
+        let mut ipv4_cidrs: Vec<CIDR<IPv4>> = Vec::new();
+        let mut ipv6_cidrs: Vec<CIDR<IPv6>> = Vec::new();
+
+        for raw_cidr in raw_cidrs {
+            let raw_cidr = raw_cidr.trim().to_string();
+            if raw_cidr.is_empty() {
+                continue;
+            }
+
+            if arg_parser::is_ipv6(&raw_cidr) {
+                ipv6_cidrs.push(arg_parser::parse_cidr("CIDR", raw_cidr)?);
+            } else {
+                ipv4_cidrs.push(arg_parser::parse_cidr("CIDR", raw_cidr)?);
+            }
+        }
+
+        if ipv4_cidrs.is_empty() && ipv6_cidrs.is_empty() {
+            return Err(ErrorKind::InvalidInput(
+                "expecting at least one CIDR".to_string(),
+            ));
+        }
+
+        if self.output == OutputFormat::Json {
+            let ipv4_summary =
+                net::summarize_with_overcoverage(&ipv4_cidrs, self.max_extra_addresses);
+            let ipv6_summary =
+                net::summarize_with_overcoverage(&ipv6_cidrs, self.max_extra_addresses);
+            Document::new(&mut self.out)
+                .array(
+                    "cidrs",
+                    ipv4_summary
+                        .iter()
+                        .map(json::cidr)
+                        .chain(ipv6_summary.iter().map(json::cidr)),
+                )
+                .end();
+            return Ok(());
+        }
+
+        self.summarize(&ipv4_cidrs, template.as_ref());
+        self.summarize(&ipv6_cidrs, template.as_ref());
+
+        Ok(())
+    }
+
+    fn summarize<A: AddressFamily>(&mut self, cidrs: &[CIDR<A>], template: Option<&Template>) {
+        for cidr in net::summarize_with_overcoverage(cidrs, self.max_extra_addresses) {
+            template::write_cidr(&mut self.out, template, &cidr);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    fn lines(raw: &str) -> Vec<String> {
+        raw.lines().map(|line| line.to_string()).collect()
+    }
+
+    #[test]
+    fn summarize_no_cidr() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(lines("\n  \n"));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting at least one CIDR".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn summarize_invalid_cidr() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute(lines("10.0.0.0/24\n10.0.1.0/33"));
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "masklength must be between 0 and 32".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn summarize_mixed_families() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute(lines(include_str!("testdata/summarize-input.txt")))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/summarize.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn summarize_with_overcoverage() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.max_extra_addresses = 512;
+        cli.execute(lines(include_str!("testdata/summarize-input.txt")))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/summarize-overcoverage.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn summarize_format() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.format = Some("{cidr} ({hosts})".to_string());
+        cli.execute(lines("10.0.0.0/24\n10.0.1.0/24\n2001:db8::/48"))
+            .unwrap();
+
+        assert_eq!(
+            "10.0.0.0/23 (510)\n2001:db8::/48 (1208925819614629174706176)\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn summarize_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.output = OutputFormat::Json;
+        cli.execute(lines("10.0.0.0/24\n10.0.1.0/24\n2001:db8::/48"))
+            .unwrap();
+
+        let expected_output = include_str!("testdata/summarize.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+}
@@ -0,0 +1,276 @@
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPKind, Mask, CIDR};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Column {
+    Network,
+    First,
+    Last,
+    Broadcast,
+    Hosts,
+    Kind,
+}
+
+const COLUMNS: [Column; 6] = [
+    Column::Network,
+    Column::First,
+    Column::Last,
+    Column::Broadcast,
+    Column::Hosts,
+    Column::Kind,
+];
+
+impl Column {
+    fn name(&self) -> &'static str {
+        match self {
+            Column::Network => "network",
+            Column::First => "first",
+            Column::Last => "last",
+            Column::Broadcast => "broadcast",
+            Column::Hosts => "hosts",
+            Column::Kind => "kind",
+        }
+    }
+
+    fn value<A: AddressFamily>(&self, cidr: &CIDR<A>) -> Option<String> {
+        match self {
+            Column::Network => Some(cidr.network_address().to_string()),
+            Column::First => cidr.first_address().map(|ip| ip.to_string()),
+            Column::Last => cidr.last_address().map(|ip| ip.to_string()),
+            Column::Broadcast => cidr.broadcast_address().map(|ip| ip.to_string()),
+            Column::Hosts => Some(cidr.hosts().to_string()),
+            Column::Kind => Some(cidr.kind().to_string()),
+        }
+    }
+
+    /// Widest value of the column in the family, header included, so that
+    /// tables are aligned without looking at their rows first.
+    fn width<A: AddressFamily>(&self) -> usize {
+        let address = A::from_u128(u128::MAX).to_string().len();
+        let width = match self {
+            Column::Network => address + 1 + A::BITS.to_string().len(),
+            Column::First | Column::Last | Column::Broadcast => address,
+            Column::Hosts => Mask::<A>::new(0).unwrap().hosts().to_string().len(),
+            Column::Kind => A::special_purpose_registry()
+                .iter()
+                .map(|block| block.kind())
+                .chain([IPKind::Private, IPKind::Public])
+                .map(|kind| kind.to_string().len())
+                .max()
+                .unwrap_or(0),
+        };
+
+        width.max(self.name().len())
+    }
+}
+
+/// Comma separated list of column names, every column being displayed if
+/// none is given.
+fn parse_columns(raw: Option<&str>) -> Result<Vec<Column>, ErrorKind> {
+    let Some(raw) = raw else {
+        return Ok(COLUMNS.to_vec());
+    };
+
+    raw.split(',')
+        .map(|name| {
+            let name = name.trim();
+            COLUMNS
+                .iter()
+                .find(|column| column.name().eq_ignore_ascii_case(name))
+                .copied()
+                .ok_or_else(|| {
+                    let names: Vec<&str> = COLUMNS.iter().map(Column::name).collect();
+                    ErrorKind::InvalidInput(format!(
+                        "unknown column {}, expecting one of {}",
+                        name,
+                        names.join(", ")
+                    ))
+                })
+        })
+        .collect()
+}
+
+/// Writes one row per CIDR, in the csv, tsv or table format. Rows are
+/// written as the CIDRs are produced, table columns being as wide as their
+/// widest value in the address family.
+pub fn write_cidrs<A: AddressFamily, W: std::io::Write>(
+    out: &mut W,
+    format: OutputFormat,
+    raw_columns: Option<&str>,
+    cidrs: impl Iterator<Item = CIDR<A>>,
+) -> Result<(), ErrorKind> {
+    let columns = parse_columns(raw_columns)?;
+    let header: Vec<String> = columns
+        .iter()
+        .map(|column| column.name().to_string())
+        .collect();
+    let row = |cidr: &CIDR<A>| -> Vec<Option<String>> {
+        columns.iter().map(|column| column.value(cidr)).collect()
+    };
+
+    match format {
+        OutputFormat::Csv | OutputFormat::Tsv => {
+            let separator = if format == OutputFormat::Csv {
+                ","
+            } else {
+                "\t"
+            };
+            writeln!(out, "{}", header.join(separator)).unwrap();
+            for cidr in cidrs {
+                let values: Vec<String> = row(&cidr)
+                    .into_iter()
+                    .map(|value| {
+                        let value = value.unwrap_or_default();
+                        if format == OutputFormat::Csv {
+                            csv_escape(value)
+                        } else {
+                            value
+                        }
+                    })
+                    .collect();
+                writeln!(out, "{}", values.join(separator)).unwrap();
+            }
+        }
+        _ => {
+            let widths: Vec<usize> = columns.iter().map(Column::width::<A>).collect();
+            let header = header.iter().map(|name| Some(name.to_uppercase()));
+            write_row(out, &widths, header);
+            for cidr in cidrs {
+                write_row(out, &widths, row(&cidr).into_iter());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_row<W: std::io::Write>(
+    out: &mut W,
+    widths: &[usize],
+    values: impl Iterator<Item = Option<String>>,
+) {
+    let line: Vec<String> = values
+        .zip(widths)
+        .map(|(value, width)| {
+            let value = value.unwrap_or_else(|| "n/a".to_string());
+            format!("{:width$}", value, width = width)
+        })
+        .collect();
+    writeln!(out, "{}", line.join("  ").trim_end()).unwrap();
+}
+
+fn csv_escape(value: String) -> String {
+    if value.contains([',', '"', '\n']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_columns, write_cidrs, Column};
+    use crate::cli::{ErrorKind, OutputFormat};
+    use crate::net::{cidrs, IPv4, IPv6};
+
+    #[test]
+    fn columns() {
+        assert_eq!(Ok(super::COLUMNS.to_vec()), parse_columns(None));
+        assert_eq!(
+            Ok(vec![Column::Network, Column::Hosts]),
+            parse_columns(Some("network, HOSTS"))
+        );
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "unknown column mask, expecting one of network, first, last, broadcast, hosts, kind"
+                    .to_string()
+            )),
+            parse_columns(Some("network,mask"))
+        );
+    }
+
+    #[test]
+    fn csv() {
+        let mut output = Vec::new();
+        write_cidrs(
+            &mut output,
+            OutputFormat::Csv,
+            None,
+            cidrs::<IPv4>(&["10.0.0.0/31", "192.0.2.0/24"]).into_iter(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "network,first,last,broadcast,hosts,kind
+10.0.0.0/31,10.0.0.0,10.0.0.1,,2,Private Internet
+192.0.2.0/24,192.0.2.1,192.0.2.254,192.0.2.255,254,Special (documentation)
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn tsv() {
+        let mut output = Vec::new();
+        write_cidrs(
+            &mut output,
+            OutputFormat::Tsv,
+            Some("network,hosts"),
+            cidrs::<IPv4>(&["10.0.0.0/31"]).into_iter(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "network\thosts\n10.0.0.0/31\t2\n",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn table() {
+        let mut output = Vec::new();
+        write_cidrs(
+            &mut output,
+            OutputFormat::Table,
+            Some("network,broadcast,hosts"),
+            cidrs::<IPv4>(&["10.0.0.0/31", "192.0.2.0/24"]).into_iter(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "NETWORK             BROADCAST        HOSTS
+10.0.0.0/31         n/a              2
+192.0.2.0/24        192.0.2.255      254
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn table_ipv6() {
+        let mut output = Vec::new();
+        write_cidrs(
+            &mut output,
+            OutputFormat::Table,
+            Some("network,hosts,first"),
+            cidrs::<IPv6>(&["2001:db8::/127"]).into_iter(),
+        )
+        .unwrap();
+
+        assert_eq!(
+            "NETWORK                                      HOSTS                                    FIRST
+2001:db8::/127                               2                                        2001:db8::
+",
+            String::from_utf8(output).unwrap()
+        );
+    }
+
+    #[test]
+    fn csv_escape() {
+        assert_eq!("plain", super::csv_escape("plain".to_string()));
+        assert_eq!(
+            "\"a, \"\"quoted\"\" value\"",
+            super::csv_escape("a, \"quoted\" value".to_string())
+        );
+    }
+}
@@ -0,0 +1,266 @@
+use crate::cli::ErrorKind;
+use crate::net::{AddressFamily, CIDR};
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Placeholder {
+    Address,
+    Cidr,
+    Network,
+    Prefix,
+    Netmask,
+    Wildcard,
+    First,
+    Last,
+    Broadcast,
+    Hosts,
+    Class,
+    Kind,
+}
+
+const PLACEHOLDERS: [Placeholder; 12] = [
+    Placeholder::Address,
+    Placeholder::Cidr,
+    Placeholder::Network,
+    Placeholder::Prefix,
+    Placeholder::Netmask,
+    Placeholder::Wildcard,
+    Placeholder::First,
+    Placeholder::Last,
+    Placeholder::Broadcast,
+    Placeholder::Hosts,
+    Placeholder::Class,
+    Placeholder::Kind,
+];
+
+impl Placeholder {
+    fn name(&self) -> &'static str {
+        match self {
+            Placeholder::Address => "address",
+            Placeholder::Cidr => "cidr",
+            Placeholder::Network => "network",
+            Placeholder::Prefix => "prefix",
+            Placeholder::Netmask => "netmask",
+            Placeholder::Wildcard => "wildcard",
+            Placeholder::First => "first",
+            Placeholder::Last => "last",
+            Placeholder::Broadcast => "broadcast",
+            Placeholder::Hosts => "hosts",
+            Placeholder::Class => "class",
+            Placeholder::Kind => "kind",
+        }
+    }
+
+    fn value<A: AddressFamily>(&self, cidr: &CIDR<A>) -> Option<String> {
+        match self {
+            Placeholder::Address => Some(cidr.ip().to_string()),
+            Placeholder::Cidr => Some(cidr.network_address().to_string()),
+            Placeholder::Network => Some(cidr.network_address().ip().to_string()),
+            Placeholder::Prefix => Some(cidr.mask().prefix_length().to_string()),
+            Placeholder::Netmask => Some(cidr.mask().to_string()),
+            Placeholder::Wildcard => Some(cidr.wildcard_mask().to_string()),
+            Placeholder::First => cidr.first_address().map(|ip| ip.to_string()),
+            Placeholder::Last => cidr.last_address().map(|ip| ip.to_string()),
+            Placeholder::Broadcast => cidr.broadcast_address().map(|ip| ip.to_string()),
+            Placeholder::Hosts => Some(cidr.hosts().to_string()),
+            Placeholder::Class => cidr.class().map(|class| class.to_string()),
+            Placeholder::Kind => Some(cidr.kind().to_string()),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Part {
+    Text(String),
+    Placeholder(Placeholder),
+}
+
+/// Line rendered for each CIDR, made of text and `{name}` placeholders.
+/// `{{` and `}}` stand for literal braces, placeholders without a value,
+/// like the broadcast address of an IPv6 network, render as nothing.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Template {
+    parts: Vec<Part>,
+}
+
+impl Template {
+    pub fn parse(raw: &str) -> Result<Self, ErrorKind> {
+        let mut parts = Vec::new();
+        let mut text = String::new();
+        let mut chars = raw.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    text.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    text.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                return Err(ErrorKind::InvalidInput(format!(
+                                    "unclosed placeholder {{{} in format",
+                                    name
+                                )))
+                            }
+                        }
+                    }
+
+                    let placeholder = PLACEHOLDERS
+                        .iter()
+                        .find(|placeholder| placeholder.name() == name)
+                        .ok_or_else(|| {
+                            let names: Vec<String> = PLACEHOLDERS
+                                .iter()
+                                .map(|placeholder| format!("{{{}}}", placeholder.name()))
+                                .collect();
+                            ErrorKind::InvalidInput(format!(
+                                "unknown placeholder {{{}}} in format, expecting one of {}",
+                                name,
+                                names.join(", ")
+                            ))
+                        })?;
+
+                    if !text.is_empty() {
+                        parts.push(Part::Text(std::mem::take(&mut text)));
+                    }
+                    parts.push(Part::Placeholder(*placeholder));
+                }
+                '}' => {
+                    return Err(ErrorKind::InvalidInput(
+                        "unmatched } in format, use }} for a literal brace".to_string(),
+                    ))
+                }
+                c => text.push(c),
+            }
+        }
+
+        if !text.is_empty() {
+            parts.push(Part::Text(text));
+        }
+
+        Ok(Template { parts })
+    }
+
+    pub fn render<A: AddressFamily>(&self, cidr: &CIDR<A>) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                Part::Text(text) => text.clone(),
+                Part::Placeholder(placeholder) => placeholder.value(cidr).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Parses the `format` field of the subcommands, a template rendered for
+/// each CIDR instead of their text output.
+pub fn parse(raw: Option<&str>) -> Result<Option<Template>, ErrorKind> {
+    raw.map(Template::parse).transpose()
+}
+
+/// Writes the rendered template, or the CIDR itself without template.
+pub fn write_cidr<A: AddressFamily, W: std::io::Write>(
+    out: &mut W,
+    template: Option<&Template>,
+    cidr: &CIDR<A>,
+) {
+    match template {
+        Some(template) => writeln!(out, "{}", template.render(cidr)).unwrap(),
+        None => writeln!(out, "{}", cidr).unwrap(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Template;
+    use crate::cli::ErrorKind;
+    use crate::net::{IPv4, IPv6, CIDR};
+
+    fn render(raw_template: &str, raw_cidr: &str) -> String {
+        let template = Template::parse(raw_template).unwrap();
+        if raw_cidr.contains(':') {
+            template.render(&raw_cidr.parse::<CIDR<IPv6>>().unwrap())
+        } else {
+            template.render(&raw_cidr.parse::<CIDR<IPv4>>().unwrap())
+        }
+    }
+
+    #[test]
+    fn render_placeholders() {
+        assert_eq!(
+            "10.12.16.0/20 10.12.16.1-10.12.31.254 4094",
+            render(
+                "{network}/{prefix} {first}-{last} {hosts}",
+                "10.12.23.43/20"
+            )
+        );
+        assert_eq!(
+            "10.12.16.0 255.255.240.0",
+            render("{network} {netmask}", "10.12.23.43/20")
+        );
+        assert_eq!(
+            "10.12.16.0/20 (4094)",
+            render("{cidr} ({hosts})", "10.12.23.43/20")
+        );
+        assert_eq!(
+            "10.12.23.43 0.0.15.255 10.12.31.255 A Private Internet",
+            render(
+                "{address} {wildcard} {broadcast} {class} {kind}",
+                "10.12.23.43/20"
+            )
+        );
+    }
+
+    #[test]
+    fn render_missing_values() {
+        assert_eq!(
+            "2001:db8::/32 broadcast= class=",
+            render(
+                "{cidr} broadcast={broadcast} class={class}",
+                "2001:db8::1/32"
+            )
+        );
+    }
+
+    #[test]
+    fn render_escaped_braces() {
+        assert_eq!(
+            "{\"cidr\": \"10.0.0.0/8\"}",
+            render("{{\"cidr\": \"{cidr}\"}}", "10.1.2.3/8")
+        );
+        assert_eq!("plain text", render("plain text", "10.1.2.3/8"));
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "unknown placeholder {mask} in format, expecting one of {address}, {cidr}, \
+                {network}, {prefix}, {netmask}, {wildcard}, {first}, {last}, {broadcast}, \
+                {hosts}, {class}, {kind}"
+                    .to_string()
+            )),
+            Template::parse("{network} {mask}")
+        );
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "unclosed placeholder {network in format".to_string()
+            )),
+            Template::parse("{network")
+        );
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "unmatched } in format, use }} for a literal brace".to_string()
+            )),
+            Template::parse("network}")
+        );
+    }
+}
@@ -0,0 +1,236 @@
+use crate::cli::arg_parser;
+use crate::cli::json::{Document, Value};
+use crate::cli::wildcard_formatter::WildcardFormatter;
+use crate::cli::{ErrorKind, OutputFormat};
+use crate::net::{AddressFamily, IPv4, IPv6};
+
+pub struct CLI<W: std::io::Write> {
+    pub out: W,
+    pub with_binary: bool,
+    pub checks: Vec<String>,
+    /// Maximum number of matched networks to list.
+    pub limit: usize,
+    pub output: OutputFormat,
+}
+
+impl<W: std::io::Write> CLI<W> {
+    pub fn new(out: W) -> Self {
+        CLI {
+            out,
+            with_binary: true,
+            checks: Vec::new(),
+            limit: 256,
+            output: OutputFormat::Text,
+        }
+    }
+
+    pub fn execute(&mut self, raw_address: String, raw_wildcard: String) -> Result<(), ErrorKind> {
+        if arg_parser::is_ipv6(&raw_address) {
+            self.wildcard::<IPv6>(raw_address, raw_wildcard)
+        } else {
+            self.wildcard::<IPv4>(raw_address, raw_wildcard)
+        }
+    }
+
+    fn wildcard<A: AddressFamily>(
+        &mut self,
+        raw_address: String,
+        raw_wildcard: String,
+    ) -> Result<(), ErrorKind> {
+        let address = arg_parser::parse_ip::<A>("ADDRESS", raw_address)?;
+        let wildcard = arg_parser::parse_wildcard_mask::<A>("WILDCARD", raw_wildcard)?;
+
+        if !self.checks.is_empty() {
+            let checks = self
+                .checks
+                .iter()
+                .map(|raw| arg_parser::parse_ip::<A>("CHECK", raw.to_string()))
+                .collect::<Result<Vec<A>, ErrorKind>>()?;
+
+            if self.output == OutputFormat::Json {
+                let checks = checks.iter().map(|check| {
+                    Value::Object(vec![
+                        ("address", Value::string(check)),
+                        ("matches", Value::Bool(wildcard.matches(&address, check))),
+                    ])
+                });
+                Document::new(&mut self.out)
+                    .field("address", Value::string(address))
+                    .field("wildcard", Value::string(wildcard))
+                    .array("checks", checks)
+                    .end();
+                return Ok(());
+            }
+
+            for check in checks {
+                let description = if wildcard.matches(&address, &check) {
+                    "matches"
+                } else {
+                    "does not match"
+                };
+
+                writeln!(
+                    self.out,
+                    "{} {} {} {}",
+                    check, description, address, wildcard
+                )
+                .unwrap();
+            }
+
+            return Ok(());
+        }
+
+        if self.output == OutputFormat::Json {
+            let networks = wildcard.matched_cidrs(&address).take(self.limit);
+            Document::new(&mut self.out)
+                .field("address", Value::string(address))
+                .field("wildcard", Value::string(wildcard))
+                .field("count", Value::string(wildcard.matched_cidrs_count()))
+                .array("networks", networks.map(Value::string))
+                .end();
+            return Ok(());
+        }
+
+        write!(
+            self.out,
+            "{}",
+            WildcardFormatter {
+                address,
+                wildcard,
+                with_binary: self.with_binary,
+            }
+        )
+        .unwrap();
+
+        for cidr in wildcard.matched_cidrs(&address).take(self.limit) {
+            writeln!(self.out, "Network:   {}", cidr).unwrap();
+        }
+
+        let count = wildcard.matched_cidrs_count();
+        if count > self.limit as u128 {
+            writeln!(self.out, "... {} more", count - self.limit as u128).unwrap();
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::cli::{ErrorKind, OutputFormat};
+
+    #[test]
+    fn wildcard_empty_address() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("".to_string(), "0.0.255.0".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "expecting non empty ADDRESS argument".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn wildcard_unparsable_address() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.0/24".to_string(), "0.0.255.0".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid IPv4 address format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn wildcard_unparsable_wildcard() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        let output = cli.execute("10.0.0.5".to_string(), "24".to_string());
+
+        assert_eq!(
+            Err(ErrorKind::InvalidInput(
+                "invalid wildcard mask format".to_string()
+            )),
+            output
+        );
+    }
+
+    #[test]
+    fn wildcard_non_contiguous() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.0.0.5".to_string(), "0.3.0.255".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/wildcard-non-contiguous.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn wildcard_checks() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.checks = vec!["10.0.42.5".to_string(), "10.0.42.6".to_string()];
+        cli.execute("10.0.0.5".to_string(), "0.0.255.0".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/wildcard-checks.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn wildcard_limit() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.limit = 2;
+        cli.execute("10.0.0.5".to_string(), "0.3.0.255".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/wildcard-limit.golden");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+
+    #[test]
+    fn wildcard_default_limit() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.execute("10.0.0.0".to_string(), "255.255.255.254".to_string())
+            .unwrap();
+
+        let actual_output = String::from_utf8(output).unwrap();
+        let networks = actual_output
+            .lines()
+            .filter(|line| line.starts_with("Network:"))
+            .count();
+
+        assert_eq!(256, networks);
+        assert!(actual_output.ends_with("... 2147483392 more\n"));
+    }
+
+    #[test]
+    fn wildcard_limit_json() {
+        let mut output = Vec::new();
+        let mut cli = super::CLI::new(&mut output);
+        cli.limit = 2;
+        cli.output = OutputFormat::Json;
+        cli.execute("10.0.0.5".to_string(), "0.3.0.255".to_string())
+            .unwrap();
+
+        let expected_output = include_str!("testdata/wildcard-limit.json");
+        let actual_output = String::from_utf8(output).unwrap();
+
+        assert_eq!(expected_output, actual_output);
+    }
+}
@@ -0,0 +1,52 @@
+use crate::cli::cidr_formatter::column_width;
+use crate::net::{AddressFamily, WildcardMask};
+use core::fmt;
+
+pub struct WildcardFormatter<A: AddressFamily> {
+    pub address: A,
+    pub wildcard: WildcardMask<A>,
+    pub with_binary: bool,
+}
+
+impl<A: AddressFamily> fmt::Display for WildcardFormatter<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = column_width::<A>();
+
+        write!(f, "Address:   {:width$}", format!("{}", self.address))?;
+        if self.with_binary {
+            write!(f, "{:b}", self.address)?;
+        }
+        writeln!(f)?;
+
+        write!(f, "Wildcard:  {:width$}", format!("{}", self.wildcard))?;
+        if self.with_binary {
+            write!(f, "{:b}", self.wildcard)?;
+        }
+        writeln!(f)?;
+
+        write!(
+            f,
+            "Netmask:   {:width$}",
+            format!("{}", self.wildcard.netmask())
+        )?;
+        if self.with_binary {
+            write!(f, "{:b}", self.wildcard.netmask())?;
+        }
+        writeln!(f)?;
+
+        writeln!(f, "=>")?;
+
+        write!(
+            f,
+            "Matches:   {:width$}",
+            format!("{}", self.wildcard.addresses())
+        )?;
+        if self.wildcard.is_contiguous() {
+            write!(f, "contiguous")?;
+        } else {
+            write!(f, "non contiguous")?;
+        }
+
+        writeln!(f)
+    }
+}
@@ -1,4 +1,4 @@
 pub mod cli;
-mod net;
+pub mod net;
 
-pub use self::net::{CIDRParsingError, CIDR};
+pub use self::net::{AddressFamily, CIDRParsingError, IPv4, IPv6, Mask, CIDR};
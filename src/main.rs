@@ -1,5 +1,9 @@
-use clap::{Parser, Subcommand};
-use ipcalc::cli::{aggregate, compare, describe, split, ErrorKind};
+use clap::{CommandFactory, Parser, Subcommand, ValueEnum};
+use ipcalc::cli::{
+    aggregate, compare, compat, describe, exclude, free, hosts, lookup, overlaps, plan, range, set,
+    split, summarize, wildcard, ErrorKind, OutputFormat,
+};
+use std::io::BufRead;
 
 #[derive(Subcommand, Debug)]
 enum CLICommand {
@@ -9,15 +13,21 @@ enum CLICommand {
         cidr: String,
         #[clap(help=DESCRIBE_NO_BINARY_HELP, long)]
         no_binary: bool,
+        #[clap(help=DESCRIBE_HOSTS_HELP, long)]
+        hosts: Option<u128>,
     },
     #[clap(about=SPLIT_HELP, long_about=SPLIT_LONG_HELP)]
     Split {
         #[clap(help=SPLIT_CIDR_HELP)]
         cidr: String,
-        #[clap(help=SPLIT_NEW_MASK)]
-        new_mask: String,
+        #[clap(help=SPLIT_NEW_MASK, required_unless_present_any=["hosts", "count"])]
+        new_mask: Option<String>,
         #[clap(help=SPLIT_NO_BINARY_HELP, long)]
         no_binary: bool,
+        #[clap(help=SPLIT_HOSTS_HELP, long, conflicts_with_all=["new_mask", "count"])]
+        hosts: Option<u128>,
+        #[clap(help=SPLIT_COUNT_HELP, long, conflicts_with="new_mask")]
+        count: Option<u128>,
     },
     #[clap(about=COMPARE_HELP, long_about=COMPARE_LONG_HELP)]
     Compare {
@@ -33,6 +43,115 @@ enum CLICommand {
         #[clap(help=AGGREGATE_MASK_HELP)]
         mask: String,
     },
+    #[clap(about=WILDCARD_HELP, long_about=WILDCARD_LONG_HELP)]
+    Wildcard {
+        #[clap(help=WILDCARD_ADDRESS_HELP)]
+        address: String,
+        #[clap(help=WILDCARD_WILDCARD_HELP)]
+        wildcard: String,
+        #[clap(help=WILDCARD_CHECK_HELP, long)]
+        check: Vec<String>,
+        #[clap(help=WILDCARD_NO_BINARY_HELP, long)]
+        no_binary: bool,
+        #[clap(help=WILDCARD_LIMIT_HELP, long, default_value_t = 256)]
+        limit: usize,
+    },
+    #[clap(about=RANGE_HELP, long_about=RANGE_LONG_HELP)]
+    Range {
+        #[clap(help=RANGE_RANGE_HELP)]
+        range: String,
+    },
+    #[clap(about=SUMMARIZE_HELP, long_about=SUMMARIZE_LONG_HELP)]
+    Summarize {
+        #[clap(help=SUMMARIZE_CIDRS_HELP)]
+        cidrs: Vec<String>,
+        #[clap(help=SUMMARIZE_FILE_HELP, long)]
+        file: Option<String>,
+        #[clap(help=SUMMARIZE_MAX_OVERCOVERAGE_HELP, long, default_value_t = 0)]
+        max_overcoverage: u128,
+    },
+    #[clap(about=EXCLUDE_HELP, long_about=EXCLUDE_LONG_HELP)]
+    Exclude {
+        #[clap(help=EXCLUDE_SUPERNET_HELP)]
+        supernet: String,
+        #[clap(help=EXCLUDE_EXCLUDED_HELP, required = true)]
+        excluded: Vec<String>,
+    },
+    #[clap(about=FREE_HELP, long_about=FREE_LONG_HELP)]
+    Free {
+        #[clap(help=FREE_PARENT_HELP)]
+        parent: String,
+        #[clap(help=FREE_USED_HELP)]
+        used: Vec<String>,
+        #[clap(help=FREE_FILE_HELP, long)]
+        file: Option<String>,
+        #[clap(help=FREE_MASK_HELP, long)]
+        mask: Option<String>,
+    },
+    #[clap(about=HOSTS_HELP, long_about=HOSTS_LONG_HELP)]
+    Hosts {
+        #[clap(help=HOSTS_CIDR_HELP)]
+        cidr: String,
+        #[clap(help=HOSTS_ALL_HELP, long)]
+        all: bool,
+        #[clap(help=HOSTS_REVERSE_HELP, long)]
+        reverse: bool,
+        #[clap(help=HOSTS_STRIDE_HELP, long, default_value_t = 1)]
+        stride: usize,
+        #[clap(help=HOSTS_OFFSET_HELP, long, default_value_t = 0)]
+        offset: usize,
+        #[clap(help=HOSTS_LIMIT_HELP, long)]
+        limit: Option<usize>,
+    },
+    #[clap(about=LOOKUP_HELP, long_about=LOOKUP_LONG_HELP)]
+    Lookup {
+        #[clap(help=LOOKUP_TABLE_HELP, long)]
+        table: String,
+        #[clap(help=LOOKUP_IPS_HELP, required = true)]
+        ips: Vec<String>,
+        #[clap(help=LOOKUP_ALL_HELP, long)]
+        all: bool,
+    },
+    #[clap(about=OVERLAPS_HELP, long_about=OVERLAPS_LONG_HELP)]
+    Overlaps {
+        #[clap(help=OVERLAPS_ENTRIES_HELP)]
+        entries: Vec<String>,
+        #[clap(help=OVERLAPS_FILE_HELP, long)]
+        file: Option<String>,
+    },
+    #[clap(about=PLAN_HELP, long_about=PLAN_LONG_HELP)]
+    Plan {
+        #[clap(help=PLAN_SUPERNET_HELP)]
+        supernet: String,
+        #[clap(help=PLAN_REQUIREMENTS_HELP)]
+        requirements: Vec<String>,
+        #[clap(help=PLAN_FILE_HELP, long)]
+        file: Option<String>,
+    },
+    #[clap(about=SET_HELP, long_about=SET_LONG_HELP)]
+    Set {
+        #[clap(subcommand)]
+        operation: SetCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum SetCommand {
+    #[clap(about=SET_UNION_HELP)]
+    Union {
+        #[clap(help=SET_SETS_HELP)]
+        sets: Vec<String>,
+    },
+    #[clap(about=SET_INTERSECT_HELP)]
+    Intersect {
+        #[clap(help=SET_SETS_HELP)]
+        sets: Vec<String>,
+    },
+    #[clap(about=SET_SUBTRACT_HELP)]
+    Subtract {
+        #[clap(help=SET_SETS_HELP)]
+        sets: Vec<String>,
+    },
 }
 
 #[derive(Parser, Debug)]
@@ -40,10 +159,154 @@ enum CLICommand {
 struct Cli {
     #[clap(subcommand)]
     command: CLICommand,
+    #[clap(help=OUTPUT_HELP, long, global = true, value_enum, default_value_t = Output::Text)]
+    output: Output,
+    #[clap(help=COLUMNS_HELP, long, global = true)]
+    columns: Option<String>,
+    #[clap(help=FORMAT_HELP, long, global = true)]
+    format: Option<String>,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Output {
+    Text,
+    Json,
+    Csv,
+    Tsv,
+    Table,
+}
+
+/// Red Hat ipcalc compatible invocation, see `is_compat`.
+#[derive(Parser, Debug)]
+#[clap(
+    name = "ipcalc",
+    override_usage = "ipcalc [-nmpbhcs] ADDRESS[/PREFIX] [NETMASK]",
+    disable_help_flag = true,
+    disable_version_flag = true
+)]
+struct CompatCli {
+    #[clap(help=COMPAT_ADDRESS_HELP)]
+    address: String,
+    #[clap(help=COMPAT_NETMASK_HELP)]
+    netmask: Option<String>,
+    #[clap(help=COMPAT_NETWORK_FLAG_HELP, short = 'n', long)]
+    network: bool,
+    #[clap(help=COMPAT_NETMASK_FLAG_HELP, short = 'm', long = "netmask")]
+    show_netmask: bool,
+    #[clap(help=COMPAT_PREFIX_FLAG_HELP, short = 'p', long)]
+    prefix: bool,
+    #[clap(help=COMPAT_BROADCAST_FLAG_HELP, short = 'b', long)]
+    broadcast: bool,
+    #[clap(help=COMPAT_HOSTNAME_FLAG_HELP, short = 'h', long)]
+    hostname: bool,
+    #[clap(help=COMPAT_MINADDR_FLAG_HELP, long)]
+    minaddr: bool,
+    #[clap(help=COMPAT_MAXADDR_FLAG_HELP, long)]
+    maxaddr: bool,
+    #[clap(help=COMPAT_ADDRESSES_FLAG_HELP, long)]
+    addresses: bool,
+    #[clap(help=COMPAT_ADDRSPACE_FLAG_HELP, long)]
+    addrspace: bool,
+    #[clap(help=COMPAT_CHECK_FLAG_HELP, short = 'c', long)]
+    check: bool,
+    #[clap(help=COMPAT_SILENT_FLAG_HELP, short = 's', long)]
+    silent: bool,
+}
+
+static COMPAT_LONG_FLAGS: [&str; 11] = [
+    "network",
+    "netmask",
+    "prefix",
+    "broadcast",
+    "hostname",
+    "minaddr",
+    "maxaddr",
+    "addresses",
+    "addrspace",
+    "check",
+    "silent",
+];
+
+/// Red Hat ipcalc invocations, like `ipcalc -n -b 192.168.1.5/24` or
+/// `ipcalc 192.168.1.5/24 -n`, pass its flags instead of a subcommand.
+/// `ipcalc -h` alone still displays the help.
+fn is_compat(args: &[String]) -> bool {
+    let Some(first) = args.get(1) else {
+        return false;
+    };
+
+    if first == "-h" {
+        return args.len() > 2;
+    }
+    if is_compat_flag(first) {
+        return true;
+    }
+    if first.starts_with('-') || Cli::command().find_subcommand(first).is_some() {
+        return false;
+    }
+
+    args[2..].iter().any(|arg| is_compat_flag(arg))
+}
+
+fn is_compat_flag(arg: &str) -> bool {
+    if let Some(long) = arg.strip_prefix("--") {
+        return COMPAT_LONG_FLAGS.contains(&long);
+    }
+
+    match arg.strip_prefix('-') {
+        Some(short) => !short.is_empty() && short.chars().all(|c| "nmpbhcs".contains(c)),
+        None => false,
+    }
+}
+
+/// Exits with 1 on any error like Red Hat ipcalc, including usage errors.
+fn run_compat(args: Vec<String>) -> i32 {
+    let cli = match CompatCli::try_parse_from(args) {
+        Ok(cli) => cli,
+        Err(err) => {
+            let _ = err.print();
+            return 1;
+        }
+    };
+
+    let mut variables = Vec::new();
+    for (enabled, variable) in [
+        (cli.show_netmask, compat::Variable::Netmask),
+        (cli.prefix, compat::Variable::Prefix),
+        (cli.broadcast, compat::Variable::Broadcast),
+        (cli.network, compat::Variable::Network),
+        (cli.minaddr, compat::Variable::MinAddr),
+        (cli.maxaddr, compat::Variable::MaxAddr),
+        (cli.addresses, compat::Variable::Addresses),
+        (cli.addrspace, compat::Variable::AddrSpace),
+        (cli.hostname, compat::Variable::Hostname),
+    ] {
+        if enabled {
+            variables.push(variable);
+        }
+    }
+
+    let mut compat = compat::CLI::new(std::io::stdout());
+    compat.variables = variables;
+    compat.check = cli.check;
+    match compat.execute(cli.address, cli.netmask) {
+        Ok(()) => 0,
+        Err(ErrorKind::InvalidInput(err) | ErrorKind::CheckFailed(err)) => {
+            if !cli.silent {
+                eprintln!("ipcalc: {}", err);
+            }
+            1
+        }
+    }
 }
 
 fn main() {
-    if let Err(ErrorKind::InvalidInput(err)) = run() {
+    let args: Vec<String> = std::env::args().collect();
+    if is_compat(&args) {
+        std::process::exit(run_compat(args));
+    }
+
+    if let Err(ErrorKind::InvalidInput(err) | ErrorKind::CheckFailed(err)) = run() {
         eprintln!("{}", err);
         std::process::exit(1);
     }
@@ -51,32 +314,240 @@ fn main() {
 
 fn run() -> Result<(), ErrorKind> {
     let cli = Cli::parse();
+    let output = match cli.output {
+        Output::Text => OutputFormat::Text,
+        Output::Json => OutputFormat::Json,
+        Output::Csv => OutputFormat::Csv,
+        Output::Tsv => OutputFormat::Tsv,
+        Output::Table => OutputFormat::Table,
+    };
+    let tabular = matches!(
+        cli.command,
+        CLICommand::Aggregate { .. } | CLICommand::Split { .. }
+    );
+    if output.is_tabular() && !tabular {
+        return Err(ErrorKind::InvalidInput(
+            "--output csv, tsv and table are only supported by split and aggregate".to_string(),
+        ));
+    }
+    if cli.columns.is_some() && !output.is_tabular() {
+        return Err(ErrorKind::InvalidInput(
+            "--columns requires --output csv, tsv or table".to_string(),
+        ));
+    }
+
+    if cli.format.is_some() {
+        if output != OutputFormat::Text {
+            return Err(ErrorKind::InvalidInput(
+                "--format cannot be combined with --output".to_string(),
+            ));
+        }
+        if matches!(
+            cli.command,
+            CLICommand::Compare { .. } | CLICommand::Wildcard { .. }
+        ) {
+            return Err(ErrorKind::InvalidInput(
+                "--format is not supported by compare and wildcard".to_string(),
+            ));
+        }
+    }
 
+    let cli_columns = cli.columns;
+    let cli_format = cli.format;
     match cli.command {
         CLICommand::Aggregate { cidr, mask } => {
-            aggregate::CLI::new(std::io::stdout()).execute(cidr, mask)
+            let mut cli = aggregate::CLI::new(std::io::stdout());
+            cli.output = output;
+            cli.columns = cli_columns;
+            cli.format = cli_format;
+            cli.execute(cidr, mask)
         }
-        CLICommand::Describe { cidr, no_binary } => {
+        CLICommand::Describe {
+            cidr,
+            no_binary,
+            hosts,
+        } => {
             let mut cli = describe::CLI::new(std::io::stdout());
             cli.with_binary = !no_binary;
+            cli.hosts = hosts;
+            cli.output = output;
+            cli.format = cli_format;
             cli.execute(cidr)
         }
         CLICommand::Split {
             cidr,
             new_mask,
             no_binary,
+            hosts,
+            count,
         } => {
+            let new_mask = match (new_mask, hosts, count) {
+                (Some(new_mask), _, _) => split::NewMask::Mask(new_mask),
+                (None, Some(hosts), _) => split::NewMask::Hosts(hosts),
+                (None, None, count) => split::NewMask::Count(count.unwrap_or_default()),
+            };
+
             let mut cli = split::CLI::new(std::io::stdout());
             cli.with_binary = !no_binary;
+            cli.output = output;
+            cli.columns = cli_columns;
+            cli.format = cli_format;
             cli.execute(cidr, new_mask)
         }
         CLICommand::Compare { cidr, other } => {
             let mut cli = compare::CLI::new(std::io::stdout());
+            cli.output = output;
             cli.execute(cidr, other)
         }
+        CLICommand::Wildcard {
+            address,
+            wildcard,
+            check,
+            no_binary,
+            limit,
+        } => {
+            let mut cli = wildcard::CLI::new(std::io::stdout());
+            cli.with_binary = !no_binary;
+            cli.checks = check;
+            cli.limit = limit;
+            cli.output = output;
+            cli.execute(address, wildcard)
+        }
+        CLICommand::Range { range } => {
+            let mut cli = range::CLI::new(std::io::stdout());
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(range)
+        }
+        CLICommand::Summarize {
+            cidrs,
+            file,
+            max_overcoverage,
+        } => {
+            let cidrs = read_inputs(cidrs, file, true)?;
+
+            let mut cli = summarize::CLI::new(std::io::stdout());
+            cli.max_extra_addresses = max_overcoverage;
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(cidrs)
+        }
+        CLICommand::Exclude { supernet, excluded } => {
+            let mut cli = exclude::CLI::new(std::io::stdout());
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(supernet, excluded)
+        }
+        CLICommand::Free {
+            parent,
+            used,
+            file,
+            mask,
+        } => {
+            let used = read_inputs(used, file, true)?;
+
+            let mut cli = free::CLI::new(std::io::stdout(), std::io::stderr());
+            cli.mask = mask;
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(parent, used)
+        }
+        CLICommand::Hosts {
+            cidr,
+            all,
+            reverse,
+            stride,
+            offset,
+            limit,
+        } => {
+            let mut cli = hosts::CLI::new(std::io::stdout());
+            cli.all = all;
+            cli.reverse = reverse;
+            cli.stride = stride;
+            cli.offset = offset;
+            cli.limit = limit;
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(cidr)
+        }
+        CLICommand::Lookup { table, ips, all } => {
+            let raw_table = std::fs::read_to_string(&table).map_err(|err| {
+                ErrorKind::InvalidInput(format!("cannot read {}: {}", table, err))
+            })?;
+
+            let mut cli = lookup::CLI::new(std::io::stdout());
+            cli.all = all;
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(raw_table, ips)
+        }
+        CLICommand::Overlaps { entries, file } => {
+            let entries = read_inputs(entries, file, true)?;
+
+            let mut cli = overlaps::CLI::new(std::io::stdout());
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(entries)
+        }
+        CLICommand::Plan {
+            supernet,
+            requirements,
+            file,
+        } => {
+            let requirements = read_inputs(requirements, file, false)?;
+
+            let mut cli = plan::CLI::new(std::io::stdout());
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(supernet, requirements)
+        }
+        CLICommand::Set { operation } => {
+            let (operation, sets) = match operation {
+                SetCommand::Union { sets } => (set::Operation::Union, sets),
+                SetCommand::Intersect { sets } => (set::Operation::Intersect, sets),
+                SetCommand::Subtract { sets } => (set::Operation::Subtract, sets),
+            };
+            let mut cli = set::CLI::new(std::io::stdout());
+            cli.output = output;
+            cli.format = cli_format;
+            cli.execute(operation, sets)
+        }
     }
 }
 
+/// Positional arguments followed by the lines of the file, if any, or by the
+/// lines of stdin when neither is given and `stdin_fallback` is set.
+fn read_inputs(
+    mut args: Vec<String>,
+    file: Option<String>,
+    stdin_fallback: bool,
+) -> Result<Vec<String>, ErrorKind> {
+    if let Some(path) = file {
+        let file = std::fs::File::open(&path)
+            .map_err(|err| ErrorKind::InvalidInput(format!("cannot open {}: {}", path, err)))?;
+        args.extend(read_lines(std::io::BufReader::new(file))?);
+    } else if args.is_empty() && stdin_fallback {
+        args = read_lines(std::io::stdin().lock())?;
+    }
+
+    Ok(args)
+}
+
+fn read_lines<R: BufRead>(reader: R) -> Result<Vec<String>, ErrorKind> {
+    reader
+        .lines()
+        .collect::<Result<_, _>>()
+        .map_err(|err| ErrorKind::InvalidInput(format!("cannot read input: {}", err)))
+}
+
+static OUTPUT_HELP: &str = "Output format, json is supported by every subcommand, csv, tsv and \
+table by split and aggregate";
+static FORMAT_HELP: &str = "Template rendered for each CIDR, e.g. '{network}/{prefix} {hosts}', \
+among {address}, {cidr}, {network}, {prefix}, {netmask}, {wildcard}, {first}, {last}, \
+{broadcast}, {hosts}, {class} and {kind}";
+static COLUMNS_HELP: &str = "Comma separated columns of the csv, tsv and table outputs, among \
+network, first, last, broadcast, hosts and kind (default: all)";
+
 static AGGREGATE_HELP: &str =
     "List all possible de/aggregation from a given CIDR to a specified MASK";
 static AGGREGATE_LONG_HELP: &str =
@@ -86,28 +557,163 @@ If the MASK length is bigger, the IP won't change only the mask will
 If the MASK length is smaller, the IP will take the network address of the mask
 ";
 
-static AGGREGATE_CIDR_HELP: &str = "Any valid host or network IPv4 CIDR";
-static AGGREGATE_MASK_HELP: &str = "Lower/Upper bound of the de/aggregation";
+static AGGREGATE_CIDR_HELP: &str = "Any valid host or network IPv4 or IPv6 CIDR";
+static AGGREGATE_MASK_HELP: &str =
+    "Lower/Upper bound of the de/aggregation, as a prefix length, netmask or wildcard mask";
 
-static DESCRIBE_HELP: &str = "Display host and network related information about the IPv4 CIDR";
-static DESCRIBE_CIDR_HELP: &str = "Any valid host or network IPv4 CIDR";
+static DESCRIBE_HELP: &str =
+    "Display host and network related information about the IPv4 or IPv6 CIDR";
+static DESCRIBE_CIDR_HELP: &str = "Any valid host or network IPv4 or IPv6 CIDR.
+The mask can be a prefix length, a netmask or a wildcard mask.";
 static DESCRIBE_NO_BINARY_HELP: &str = "Hide the binary representation";
+static DESCRIBE_HOSTS_HELP: &str =
+    "Replace the CIDR mask by the smallest network providing this number of hosts";
 
 static SPLIT_HELP: &str = "Subdivide the CIDR in smaller networks and display them";
 static SPLIT_LONG_HELP: &str = "Subdivide the CIDR in smaller networks and display them
 
 If the CIDR is a network address: display all available sub-networks
 If the CIDR is a host address: display the new network in which the IP belongs
+The new mask can be given directly, or computed from a number of hosts or subnets.
 ";
 static SPLIT_CIDR_HELP: &str = DESCRIBE_CIDR_HELP;
-static SPLIT_NEW_MASK: &str = "New prefix length to apply to the CIDR
+static SPLIT_NEW_MASK: &str =
+    "New prefix length, netmask (255.255.255.0) or wildcard mask (0.0.0.255) to apply to the CIDR
 ";
 static SPLIT_NO_BINARY_HELP: &str = DESCRIBE_NO_BINARY_HELP;
+static SPLIT_HOSTS_HELP: &str = "Split in the smallest networks providing this number of hosts";
+static SPLIT_COUNT_HELP: &str = "Split in the largest networks making at least this many subnets";
 
 static COMPARE_HELP: &str = "Compare two CIDRs and display the relationship between them";
 static COMPARE_LONG_HELP: &str =
     "Compare two CIDRs and display the relationship between the first and second CIDR:
-same network, different network, subset or superset";
-static COMPARE_CIDR_HELP: &str = "Any valid host or network IPv4 CIDR.
+same network, adjacent networks, different network, subset or superset.
+Also display their smallest common supernet and the number of addresses between them";
+static COMPARE_CIDR_HELP: &str = "Any valid host or network IPv4 or IPv6 CIDR.
 If an host CIDR is given, its related network will be used.";
 static COMPARE_CIDR_OTHER_HELP: &str = COMPARE_CIDR_HELP;
+
+static WILDCARD_HELP: &str = "Describe an ACL entry made of an address and a wildcard mask";
+static WILDCARD_LONG_HELP: &str = "Describe an ACL entry made of an address and a wildcard mask
+
+The wildcard mask doesn't have to be contiguous: 0.0.255.0 matches any value in the third octet.
+List the networks matched by the entry, or check whether the given addresses match it.";
+static WILDCARD_ADDRESS_HELP: &str = "Any valid IPv4 or IPv6 address";
+static WILDCARD_WILDCARD_HELP: &str = "Wildcard mask where each bit set to 1 is ignored";
+static WILDCARD_CHECK_HELP: &str = "Address to match against the entry, can be repeated";
+static WILDCARD_NO_BINARY_HELP: &str = DESCRIBE_NO_BINARY_HELP;
+static WILDCARD_LIMIT_HELP: &str = "Maximum number of matched networks to list";
+
+static RANGE_HELP: &str = "Convert an address range to CIDRs, or a CIDR to its address range";
+static RANGE_LONG_HELP: &str = "Convert an address range to CIDRs, or a CIDR to its address range
+
+If a range (10.0.0.5-10.0.1.17) is given: display the smallest list of CIDRs covering it
+If a CIDR is given: display its first and last addresses, network and broadcast included
+";
+static RANGE_RANGE_HELP: &str =
+    "IPv4 or IPv6 range as FIRST-LAST or FIRST - LAST, or any valid CIDR";
+
+static SUMMARIZE_HELP: &str =
+    "Collapse a list of CIDRs into the smallest set covering the same addresses";
+static SUMMARIZE_LONG_HELP: &str =
+    "Collapse a list of CIDRs into the smallest set covering the same addresses
+
+Overlapping and adjacent networks are merged, IPv4 and IPv6 CIDRs are summarized separately.
+CIDRs are read from the arguments, the --file option or the standard input, one per line.
+";
+static SUMMARIZE_CIDRS_HELP: &str = "Any valid host or network IPv4 or IPv6 CIDRs";
+static SUMMARIZE_FILE_HELP: &str = "File listing one CIDR per line";
+static SUMMARIZE_MAX_OVERCOVERAGE_HELP: &str =
+    "Merge networks into a summary route as long as it covers at most this many extra addresses";
+
+static SET_HELP: &str = "Combine sets of addresses and display the result as CIDRs";
+static SET_LONG_HELP: &str = "Combine sets of addresses and display the result as CIDRs
+
+Each SET is a comma separated list of CIDRs and ranges: 10.0.0.0/24,10.0.1.5-10.0.1.17
+";
+static SET_UNION_HELP: &str = "Display the addresses in any of the sets";
+static SET_INTERSECT_HELP: &str = "Display the addresses in all the sets";
+static SET_SUBTRACT_HELP: &str = "Display the addresses of the first set that aren't in the others";
+static SET_SETS_HELP: &str = "At least two comma separated lists of IPv4 or IPv6 CIDRs and ranges";
+
+static EXCLUDE_HELP: &str = "Remove one or more CIDRs from a supernet and display what remains";
+static EXCLUDE_LONG_HELP: &str = "Remove one or more CIDRs from a supernet and display what remains
+
+Display the smallest list of CIDRs covering the SUPERNET without the EXCLUDED CIDRs.
+EXCLUDED CIDRs outside of the SUPERNET are ignored.
+";
+static EXCLUDE_SUPERNET_HELP: &str = "Any valid IPv4 or IPv6 CIDR";
+static EXCLUDE_EXCLUDED_HELP: &str = "CIDRs to remove from the SUPERNET";
+
+static LOOKUP_HELP: &str = "Resolve IPs against a table of CIDRs using the longest prefix match";
+static LOOKUP_LONG_HELP: &str =
+    "Resolve IPs against a table of CIDRs using the longest prefix match
+
+The table file lists one CIDR per line, optionally followed by a label:
+10.1.0.0/16  datacenter
+Blank lines and lines starting with # are ignored.
+";
+static LOOKUP_TABLE_HELP: &str = "File listing the CIDRs to match against";
+static LOOKUP_IPS_HELP: &str = "IPv4 or IPv6 addresses to resolve";
+static LOOKUP_ALL_HELP: &str = "Display every matching CIDR, from the least to the most specific";
+
+static HOSTS_HELP: &str = "List the host addresses of the CIDR network";
+static HOSTS_LONG_HELP: &str = "List the host addresses of the CIDR network
+
+Addresses are listed from the first to the last usable host. The OFFSET first addresses are
+skipped, then every STRIDE address is displayed until LIMIT addresses have been displayed.
+";
+static HOSTS_CIDR_HELP: &str = DESCRIBE_CIDR_HELP;
+static HOSTS_ALL_HELP: &str = "Include the network and broadcast addresses";
+static HOSTS_REVERSE_HELP: &str = "List the addresses from the last to the first one";
+static HOSTS_STRIDE_HELP: &str = "Only display one address every STRIDE addresses";
+static HOSTS_OFFSET_HELP: &str = "Number of addresses to skip";
+static HOSTS_LIMIT_HELP: &str = "Maximum number of addresses to display";
+
+static PLAN_HELP: &str = "Allocate named subnets in a supernet from their number of hosts";
+static PLAN_LONG_HELP: &str = "Allocate named subnets in a supernet from their number of hosts
+
+Each subnet gets the smallest mask providing enough hosts. Subnets are allocated largest first,
+then the remaining free space is displayed. A plan holds at most 65536 subnets.
+";
+static PLAN_SUPERNET_HELP: &str = "Any valid IPv4 or IPv6 CIDR to allocate the subnets from";
+static PLAN_REQUIREMENTS_HELP: &str =
+    "Subnets as NAME:HOSTS (users:500) or NAME:COUNTxHOSTS for COUNT subnets (p2p:4x2)";
+static PLAN_FILE_HELP: &str = "File listing one subnet requirement per line";
+
+static FREE_HELP: &str = "List the free space left in a network by the used prefixes";
+static FREE_LONG_HELP: &str = "List the free space left in a network by the used prefixes
+
+Display the largest aligned free networks, then the number of free networks per prefix length.
+Used prefixes are read from standard input if neither USED nor --file is given.
+A warning is printed for each used prefix that isn't within PARENT.
+";
+static FREE_PARENT_HELP: &str = "Any valid IPv4 or IPv6 CIDR the used prefixes are allocated from";
+static FREE_USED_HELP: &str = "Allocated IPv4 or IPv6 CIDRs";
+static FREE_FILE_HELP: &str = "File listing one used CIDR per line";
+static FREE_MASK_HELP: &str =
+    "Only list the free networks of this prefix length, netmask or wildcard mask";
+
+static OVERLAPS_HELP: &str = "Report every pair of overlapping CIDRs in a list";
+static OVERLAPS_LONG_HELP: &str = "Report every pair of overlapping CIDRs in a list
+
+Entries are either NAME,CIDR (CSV, later columns and NAME,CIDR headers are ignored) or a CIDR
+optionally followed by a label, and are read from standard input if neither ENTRIES nor --file is
+given. Exits with a non-zero status when an overlap is found.
+";
+static OVERLAPS_ENTRIES_HELP: &str = "Labelled IPv4 or IPv6 CIDRs, as NAME,CIDR or CIDR [LABEL]";
+static OVERLAPS_FILE_HELP: &str = "File listing one entry per line";
+
+static COMPAT_ADDRESS_HELP: &str = "IPv4 or IPv6 address, optionally followed by /PREFIX";
+static COMPAT_NETMASK_HELP: &str = "Netmask of the address when it has no prefix";
+static COMPAT_NETWORK_FLAG_HELP: &str = "Display the network address as NETWORK=";
+static COMPAT_NETMASK_FLAG_HELP: &str = "Display the netmask as NETMASK=";
+static COMPAT_PREFIX_FLAG_HELP: &str = "Display the prefix length as PREFIX=";
+static COMPAT_BROADCAST_FLAG_HELP: &str = "Display the broadcast address as BROADCAST=";
+static COMPAT_HOSTNAME_FLAG_HELP: &str = "Display the hostname found via DNS as HOSTNAME=";
+static COMPAT_MINADDR_FLAG_HELP: &str = "Display the first host address as MINADDR=";
+static COMPAT_MAXADDR_FLAG_HELP: &str = "Display the last host address as MAXADDR=";
+static COMPAT_ADDRESSES_FLAG_HELP: &str = "Display the number of host addresses as ADDRESSES=";
+static COMPAT_ADDRSPACE_FLAG_HELP: &str = "Display the kind of address space as ADDRSPACE=";
+static COMPAT_CHECK_FLAG_HELP: &str = "Only validate the address, exiting with 1 when invalid";
+static COMPAT_SILENT_FLAG_HELP: &str = "Don't display error messages";
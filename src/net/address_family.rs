@@ -0,0 +1,114 @@
+use crate::net::IPClass;
+use crate::net::IPKind;
+use crate::net::IPParsingError;
+use crate::net::SpecialPurposeBlock;
+use core::fmt;
+use core::hash::Hash;
+
+/// An IP address family (IPv4, IPv6) that `Mask` and `CIDR` are generic over.
+///
+/// The address is stored as an unsigned integer of `BITS` bits (`Raw`),
+/// displayed and parsed using its usual textual notation. Algorithms work on
+/// the `u128` widening of `Raw`, so they only have to be written once.
+///
+/// ```
+/// use ipcalc::net::{AddressFamily, CIDR};
+/// use ipcalc::{IPv4, IPv6};
+///
+/// fn network<A: AddressFamily>(raw: &str) -> String {
+///     raw.parse::<CIDR<A>>().unwrap().network_address().to_string()
+/// }
+///
+/// assert_eq!("10.0.0.0/8", network::<IPv4>("10.42.0.1/8"));
+/// assert_eq!("2001:db8::/32", network::<IPv6>("2001:db8::1/32"));
+/// ```
+pub trait AddressFamily:
+    Copy
+    + Eq
+    + Ord
+    + Hash
+    + fmt::Debug
+    + fmt::Display
+    + fmt::Binary
+    + std::str::FromStr<Err = IPParsingError>
+{
+    /// Unsigned integer holding the address bits.
+    type Raw: Copy + Eq + Ord + Hash + fmt::Debug + Into<u128> + TryFrom<u128>;
+
+    const NAME: &'static str;
+    const BITS: u8;
+    /// Whether the last address of a network is reserved for broadcast.
+    const HAS_BROADCAST: bool;
+    /// `ADDRSPACE` of Red Hat ipcalc for the addresses outside the blocks
+    /// naming one, see `SpecialPurposeBlock::address_space`.
+    const DEFAULT_ADDRESS_SPACE: &'static str;
+
+    fn from_raw(raw: Self::Raw) -> Self;
+
+    fn to_raw(&self) -> Self::Raw;
+
+    fn class(&self) -> Option<IPClass>;
+
+    /// IANA special-purpose address registry of the family.
+    fn special_purpose_registry() -> &'static [SpecialPurposeBlock];
+
+    /// Most specific IANA special-purpose block the address belongs to.
+    fn special_purpose(&self) -> Option<&'static SpecialPurposeBlock> {
+        Self::special_purpose_registry()
+            .iter()
+            .filter(|block| block.contains(self))
+            .max_by_key(|block| block.prefix_length)
+    }
+
+    fn kind(&self) -> IPKind {
+        self.special_purpose()
+            .map(SpecialPurposeBlock::kind)
+            .unwrap_or(IPKind::Public)
+    }
+
+    fn from_u128(value: u128) -> Self {
+        Self::from_raw(raw_from_u128::<Self>(value))
+    }
+
+    fn to_u128(&self) -> u128 {
+        self.to_raw().into()
+    }
+}
+
+/// Converts a `u128` into the family raw representation, dropping the bits
+/// above `A::BITS`.
+pub fn raw_from_u128<A: AddressFamily>(value: u128) -> A::Raw {
+    A::Raw::try_from(value & all_ones::<A>())
+        .unwrap_or_else(|_| unreachable!("value is truncated to the family width"))
+}
+
+/// `u128` with the `A::BITS` lowest bits set.
+pub fn all_ones<A: AddressFamily>() -> u128 {
+    u128::MAX >> (128 - A::BITS as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{all_ones, raw_from_u128, AddressFamily};
+    use crate::net::{IPv4, IPv6};
+
+    #[test]
+    fn all_ones_per_family() {
+        assert_eq!(0xFFFFFFFF, all_ones::<IPv4>());
+        assert_eq!(u128::MAX, all_ones::<IPv6>());
+    }
+
+    #[test]
+    fn raw_from_u128_truncates() {
+        assert_eq!(0x0A000001, raw_from_u128::<IPv4>(0x1_0A000001));
+    }
+
+    #[test]
+    fn u128_roundtrip() {
+        let ip = IPv4::new(10, 0, 0, 1);
+        assert_eq!(ip, IPv4::from_u128(ip.to_u128()));
+
+        let ip = "2001:db8::1".parse::<IPv6>().unwrap();
+        assert_eq!(ip, IPv6::from_u128(ip.to_u128()));
+    }
+}
@@ -1,14 +1,20 @@
+use crate::net::AddressFamily;
 use crate::net::IPClass;
 use crate::net::IPKind;
+use crate::net::IPSet;
 use crate::net::IPv4;
 use crate::net::Mask;
+use crate::net::MaskParsingError;
+use crate::net::SpecialPurposeOverlap;
 use core::fmt;
 
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub enum CIDRComparison {
     Subset,
     Superset,
     Equals,
+    /// The networks don't overlap, see `is_adjacent_to` to know whether
+    /// addresses lie between them.
     Different,
 }
 
@@ -16,21 +22,38 @@ pub enum CIDRComparison {
 pub enum CIDRParsingError {
     InvalidMaskLength,
     InvalidHostFormat,
+    NonContiguousMask(u8),
 }
 
-#[derive(Debug, PartialEq, Eq)]
-pub struct CIDR {
-    ip: IPv4,
-    mask: Mask,
+impl fmt::Display for CIDRParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CIDRParsingError::InvalidMaskLength => write!(f, "invalid mask length"),
+            CIDRParsingError::InvalidHostFormat => write!(f, "invalid CIDR format"),
+            CIDRParsingError::NonContiguousMask(bit) => write!(
+                f,
+                "mask must be contiguous but bit {} breaks contiguity",
+                bit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for CIDRParsingError {}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct CIDR<A: AddressFamily = IPv4> {
+    ip: A,
+    mask: Mask<A>,
 }
 
-impl fmt::Display for CIDR {
+impl<A: AddressFamily> fmt::Display for CIDR<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}/{}", self.ip, self.mask.prefix_length())
     }
 }
 
-impl std::str::FromStr for CIDR {
+impl<A: AddressFamily> std::str::FromStr for CIDR<A> {
     type Err = CIDRParsingError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
@@ -40,42 +63,43 @@ impl std::str::FromStr for CIDR {
         }
 
         let ip = values[0]
-            .parse::<IPv4>()
+            .parse::<A>()
             .map_err(|_| CIDRParsingError::InvalidHostFormat)?;
 
         let mask = if values.len() == 2 {
-            values[1]
-                .parse::<Mask>()
-                .map_err(|_| CIDRParsingError::InvalidMaskLength)?
+            values[1].parse::<Mask<A>>().map_err(|err| match err {
+                MaskParsingError::NonContiguous(bit) => CIDRParsingError::NonContiguousMask(bit),
+                _ => CIDRParsingError::InvalidMaskLength,
+            })?
         } else {
-            Mask::new(32).unwrap()
+            Mask::new(A::BITS).unwrap()
         };
 
         Ok(Self { ip, mask })
     }
 }
 
-impl CIDR {
-    pub fn new(ip: IPv4, mask: Mask) -> Self {
+impl<A: AddressFamily> CIDR<A> {
+    pub fn new(ip: A, mask: Mask<A>) -> Self {
         Self { ip, mask }
     }
 
-    pub fn network_address(&self) -> CIDR {
+    pub fn network_address(&self) -> CIDR<A> {
         CIDR {
             ip: self.mask.network_address(&self.ip),
             mask: self.mask,
         }
     }
 
-    pub fn ip(&self) -> IPv4 {
-        IPv4::new_from_raw_bytes(self.ip.octets())
+    pub fn ip(&self) -> A {
+        self.ip
     }
 
-    pub fn mask(&self) -> Mask {
+    pub fn mask(&self) -> Mask<A> {
         self.mask
     }
 
-    pub fn wildcard_mask(&self) -> Mask {
+    pub fn wildcard_mask(&self) -> Mask<A> {
         self.mask.wildcard()
     }
 
@@ -83,23 +107,41 @@ impl CIDR {
         &self.network_address() == self
     }
 
-    pub fn first_address(&self) -> Option<IPv4> {
+    pub fn first_address(&self) -> Option<A> {
         self.mask.first_address(&self.ip)
     }
 
-    pub fn last_address(&self) -> Option<IPv4> {
+    pub fn last_address(&self) -> Option<A> {
         self.mask.last_address(&self.ip)
     }
 
-    pub fn broadcast_address(&self) -> Option<IPv4> {
+    pub fn broadcast_address(&self) -> Option<A> {
         self.mask.broadcast_address(&self.ip)
     }
 
-    pub fn hosts(&self) -> u32 {
+    pub fn hosts(&self) -> u128 {
         self.mask.hosts()
     }
 
-    pub fn class(&self) -> IPClass {
+    /// Lazily lists every address of the network, network and broadcast
+    /// addresses included. Use `rev` and `step_by` to walk it backwards or
+    /// with a stride.
+    pub fn addresses(&self) -> Addresses<A> {
+        let (first, last) = self.range();
+
+        Addresses::new(first, last)
+    }
+
+    /// Lazily lists the usable host addresses of the network, from
+    /// `first_address` to `last_address`.
+    pub fn host_addresses(&self) -> Addresses<A> {
+        match (self.first_address(), self.last_address()) {
+            (Some(first), Some(last)) => Addresses::new(first.to_u128(), last.to_u128()),
+            _ => Addresses::empty(),
+        }
+    }
+
+    pub fn class(&self) -> Option<IPClass> {
         self.ip.class()
     }
 
@@ -107,47 +149,180 @@ impl CIDR {
         self.ip.kind()
     }
 
-    pub fn split(&self, mask: Mask) -> Vec<Self> {
-        let base_raw_network_ip = self.network_address().ip.octets();
-        let mut networks = Vec::new();
+    /// Every special-purpose block overlapping the network, ordered by address.
+    pub fn special_purposes(&self) -> Vec<SpecialPurposeOverlap> {
+        let mut overlaps: Vec<SpecialPurposeOverlap> = A::special_purpose_registry()
+            .iter()
+            .map(|block| SpecialPurposeOverlap {
+                block,
+                relation: self.compare(&block.network()),
+            })
+            .filter(|overlap| self.overlaps(&overlap.block.network()))
+            .collect();
+
+        overlaps.sort_by_key(|overlap| (overlap.block.address, overlap.block.prefix_length));
+        overlaps
+    }
+
+    /// Whether part of the network isn't covered by any special-purpose block.
+    pub fn has_public_remainder(&self) -> bool {
+        let overlaps = self.special_purposes();
+        if overlaps
+            .iter()
+            .any(|overlap| overlap.relation != CIDRComparison::Superset)
+        {
+            return false;
+        }
+
+        let (first, last) = self.range();
+        let mut next_uncovered = Some(first);
+        for overlap in overlaps {
+            let (start, end) = overlap.block.network::<A>().range();
 
-        let mut raw_network_ip = base_raw_network_ip;
-        while raw_network_ip < base_raw_network_ip + self.mask.hosts() {
-            networks.push(Self::new(IPv4::new_from_raw_bytes(raw_network_ip), mask));
-            raw_network_ip += mask.hosts() + 2;
+            match next_uncovered {
+                Some(next) if start > next => return true,
+                Some(next) if end >= next => next_uncovered = end.checked_add(1),
+                _ => {}
+            }
         }
 
-        networks
+        matches!(next_uncovered, Some(next) if next <= last)
     }
 
-    fn contains(&self, other: &IPv4) -> bool {
-        let start = self.network_address().ip.octets();
-        let end = self.broadcast_address().map(|ip| ip.octets());
-        if end.is_none() {
-            return false;
+    /// Lazily lists the networks of the given mask within the network. When
+    /// the mask is shorter, yields the network address with the new mask.
+    pub fn split(&self, mask: Mask<A>) -> Split<A> {
+        let first = self.network_address().ip.to_u128();
+        let last = if mask < self.mask {
+            first
+        } else {
+            (first | self.mask.wildcard().bits()) & mask.bits()
+        };
+
+        Split {
+            front: first,
+            back: last,
+            mask,
+            exhausted: false,
+        }
+    }
+
+    /// Smallest list of CIDRs covering the network without the excluded
+    /// ones, in ascending order. Excluded CIDRs outside the network are
+    /// ignored.
+    pub fn exclude(&self, excluded: &[CIDR<A>]) -> Vec<Self> {
+        let remainder = IPSet::from(*self).difference(&excluded.iter().copied().collect());
+
+        remainder.cidrs().collect()
+    }
+
+    // first and last addresses of the network, including network and
+    // broadcast addresses
+    fn range(&self) -> (u128, u128) {
+        let start = self.network_address().ip.to_u128();
+
+        (start, start | self.mask.wildcard().bits())
+    }
+
+    pub fn contains_ip(&self, ip: &A) -> bool {
+        let (start, end) = self.range();
+        let ip = ip.to_u128();
+
+        start <= ip && ip <= end
+    }
+
+    pub fn contains_cidr(&self, other: &CIDR<A>) -> bool {
+        let (start, end) = self.range();
+        let (other_start, other_end) = other.range();
+
+        start <= other_start && other_end <= end
+    }
+
+    pub fn overlaps(&self, other: &CIDR<A>) -> bool {
+        let (start, end) = self.range();
+        let (other_start, other_end) = other.range();
+
+        start <= other_end && other_start <= end
+    }
+
+    /// Whether the networks don't overlap and no address lies between them.
+    pub fn is_adjacent_to(&self, other: &CIDR<A>) -> bool {
+        let (start, end) = self.range();
+        let (other_start, other_end) = other.range();
+
+        end.checked_add(1) == Some(other_start) || other_end.checked_add(1) == Some(start)
+    }
+
+    /// Network shared by both CIDRs. As networks are aligned on their mask,
+    /// it's the most specific of the two when they overlap.
+    pub fn intersection(&self, other: &CIDR<A>) -> Option<CIDR<A>> {
+        if !self.overlaps(other) {
+            return None;
+        }
+
+        if self.mask < other.mask {
+            Some(other.network_address())
+        } else {
+            Some(self.network_address())
+        }
+    }
+
+    /// Smallest network containing both networks.
+    pub fn common_supernet(&self, other: &CIDR<A>) -> CIDR<A> {
+        let common_bits =
+            (self.ip.to_u128() ^ other.ip.to_u128()).leading_zeros() - (128 - A::BITS as u32);
+        let prefix_length = (common_bits as u8)
+            .min(self.mask.prefix_length())
+            .min(other.mask.prefix_length());
+
+        CIDR::new(self.ip, Mask::new(prefix_length).unwrap()).network_address()
+    }
+
+    /// Network covering exactly the addresses of both networks, if any: the
+    /// networks have to be the two halves of their common supernet.
+    pub fn merge(&self, other: &CIDR<A>) -> Option<CIDR<A>> {
+        let supernet = self.common_supernet(other);
+        let prefix_length = self.mask.prefix_length();
+
+        if self.mask == other.mask && prefix_length == supernet.mask.prefix_length() + 1 {
+            Some(supernet)
+        } else {
+            None
         }
-        let other_ip = other.octets();
+    }
+
+    /// Number of addresses between the networks, 0 if they are adjacent or
+    /// overlap.
+    pub fn gap(&self, other: &CIDR<A>) -> u128 {
+        let (start, end) = self.range();
+        let (other_start, other_end) = other.range();
 
-        start <= other_ip && other_ip <= end.unwrap()
+        if end < other_start {
+            other_start - end - 1
+        } else if other_end < start {
+            start - other_end - 1
+        } else {
+            0
+        }
     }
 
-    pub fn compare(&self, other: &CIDR) -> CIDRComparison {
+    pub fn compare(&self, other: &CIDR<A>) -> CIDRComparison {
         let network = self.network_address();
         let other_network = other.network_address();
 
         if network == other_network {
             CIDRComparison::Equals
-        } else if self.mask < other.mask && network.contains(&other.ip()) {
+        } else if self.mask < other.mask && network.contains_ip(&other.ip()) {
             CIDRComparison::Superset
-        } else if other.mask < self.mask && other_network.contains(&self.ip()) {
+        } else if other.mask < self.mask && other_network.contains_ip(&self.ip()) {
             CIDRComparison::Subset
         } else {
             CIDRComparison::Different
         }
     }
 
-    pub fn aggregate(&self, mask: Mask) -> CIDR {
-        let cidr = Self::new(IPv4::new_from_raw_bytes(self.ip.octets()), mask);
+    pub fn aggregate(&self, mask: Mask<A>) -> CIDR<A> {
+        let cidr = Self::new(self.ip, mask);
         if mask < self.mask {
             cidr.network_address()
         } else {
@@ -156,10 +331,215 @@ impl CIDR {
     }
 }
 
+/// Double-ended iterator over the subnets of a network, see `CIDR::split`.
+#[derive(Debug, Clone)]
+pub struct Split<A: AddressFamily = IPv4> {
+    // network addresses of the next subnets yielded from each end
+    front: u128,
+    back: u128,
+    mask: Mask<A>,
+    exhausted: bool,
+}
+
+impl<A: AddressFamily> Split<A> {
+    /// Number of subnets left, saturating at `u128::MAX`.
+    pub fn remaining(&self) -> u128 {
+        if self.exhausted {
+            return 0;
+        }
+
+        // A /0 IPv6 mask has 128 host bits, the range then holds one subnet.
+        let host_bits = 128 - self.mask.wildcard().bits().leading_zeros();
+        (self.back - self.front)
+            .checked_shr(host_bits)
+            .unwrap_or(0)
+            .saturating_add(1)
+    }
+
+    fn step(&self) -> u128 {
+        self.mask.wildcard().bits() + 1
+    }
+}
+
+impl<A: AddressFamily> Iterator for Split<A> {
+    type Item = CIDR<A>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let cidr = CIDR::new(A::from_u128(self.front), self.mask);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.front += self.step();
+        }
+
+        Some(cidr)
+    }
+
+    /// Exact when the number of subnets left fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<A: AddressFamily> DoubleEndedIterator for Split<A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let cidr = CIDR::new(A::from_u128(self.back), self.mask);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.back -= self.step();
+        }
+
+        Some(cidr)
+    }
+}
+
+// An IPv6 network can hold more subnets than a `usize` counts, only IPv4
+// ones are exact size, use `Split::remaining` for the others.
+#[cfg(target_pointer_width = "64")]
+impl ExactSizeIterator for Split<IPv4> {}
+
+impl<A: AddressFamily> std::iter::FusedIterator for Split<A> {}
+
+/// Double-ended iterator over a range of addresses, see `CIDR::addresses`.
+#[derive(Debug, Clone)]
+pub struct Addresses<A: AddressFamily = IPv4> {
+    front: u128,
+    back: u128,
+    exhausted: bool,
+    family: std::marker::PhantomData<A>,
+}
+
+impl<A: AddressFamily> Addresses<A> {
+    fn new(front: u128, back: u128) -> Self {
+        Self {
+            front,
+            back,
+            exhausted: false,
+            family: std::marker::PhantomData,
+        }
+    }
+
+    fn empty() -> Self {
+        Self {
+            exhausted: true,
+            ..Self::new(0, 0)
+        }
+    }
+
+    /// Number of addresses left, saturating at `u128::MAX`.
+    pub fn remaining(&self) -> u128 {
+        if self.exhausted {
+            return 0;
+        }
+
+        (self.back - self.front).saturating_add(1)
+    }
+}
+
+impl<A: AddressFamily> Iterator for Addresses<A> {
+    type Item = A;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let address = A::from_u128(self.front);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.front += 1;
+        }
+
+        Some(address)
+    }
+
+    // skips in constant time so that `skip` and `step_by` stay cheap
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        if self.exhausted || self.back - self.front < n as u128 {
+            self.exhausted = true;
+            return None;
+        }
+
+        self.front += n as u128;
+        self.next()
+    }
+
+    /// Exact when the number of addresses left fits in a `usize`.
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        match usize::try_from(self.remaining()) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+impl<A: AddressFamily> DoubleEndedIterator for Addresses<A> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.exhausted {
+            return None;
+        }
+
+        let address = A::from_u128(self.back);
+        if self.front == self.back {
+            self.exhausted = true;
+        } else {
+            self.back -= 1;
+        }
+
+        Some(address)
+    }
+
+    fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+        if self.exhausted || self.back - self.front < n as u128 {
+            self.exhausted = true;
+            return None;
+        }
+
+        self.back -= n as u128;
+        self.next_back()
+    }
+}
+
+// Like `Split`, only IPv4 ranges always hold fewer addresses than a `usize`
+// counts, use `Addresses::remaining` for IPv6 ones.
+#[cfg(target_pointer_width = "64")]
+impl ExactSizeIterator for Addresses<IPv4> {}
+
+impl<A: AddressFamily> std::iter::FusedIterator for Addresses<A> {}
+
+#[cfg(feature = "serde")]
+impl<A: AddressFamily> serde::Serialize for CIDR<A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, A: AddressFamily> serde::Deserialize<'de> for CIDR<A> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{CIDRComparison, CIDRParsingError, CIDR};
-    use crate::net::{IPClass, IPKind, IPv4, Mask};
+    use crate::net::{AddressFamily, IPClass, IPKind, IPv4, IPv6, Mask};
 
     #[test]
     fn parse_mask_negative() {
@@ -198,6 +578,24 @@ mod tests {
         )
     }
 
+    #[test]
+    fn parse_dotted_mask() {
+        assert_eq!(
+            Ok(CIDR::new(IPv4::new(10, 0, 5, 2), Mask::new(16).unwrap())),
+            "10.0.5.2/255.255.0.0".parse::<CIDR>(),
+        );
+
+        assert_eq!(
+            Ok(CIDR::new(IPv4::new(10, 0, 5, 2), Mask::new(24).unwrap())),
+            "10.0.5.2/0.0.0.255".parse::<CIDR>(),
+        );
+
+        assert_eq!(
+            Err(CIDRParsingError::NonContiguousMask(25)),
+            "10.0.5.2/255.255.0.255".parse::<CIDR>(),
+        );
+    }
+
     #[test]
     fn parse_ip_contains_several_slashes() {
         assert_eq!(
@@ -249,7 +647,7 @@ mod tests {
     fn class() {
         let address = CIDR::new(IPv4::new(10, 0, 10, 15), Mask::new(24).unwrap());
 
-        assert_eq!(IPClass::A, address.class())
+        assert_eq!(Some(IPClass::A), address.class())
     }
 
     #[test]
@@ -265,14 +663,14 @@ mod tests {
 
         let new_mask = Mask::new(20).unwrap();
         let expected = vec![CIDR::new(IPv4::new(10, 0, 10, 0), Mask::new(20).unwrap())];
-        assert_eq!(expected, address.split(new_mask));
+        assert_eq!(expected, address.split(new_mask).collect::<Vec<_>>());
 
         let new_mask = Mask::new(25).unwrap();
         let expected = vec![
             CIDR::new(IPv4::new(10, 0, 10, 0), Mask::new(25).unwrap()),
             CIDR::new(IPv4::new(10, 0, 10, 128), Mask::new(25).unwrap()),
         ];
-        assert_eq!(expected, address.split(new_mask));
+        assert_eq!(expected, address.split(new_mask).collect::<Vec<_>>());
 
         let new_mask = Mask::new(26).unwrap();
         let expected = vec![
@@ -281,16 +679,16 @@ mod tests {
             CIDR::new(IPv4::new(10, 0, 10, 128), Mask::new(26).unwrap()),
             CIDR::new(IPv4::new(10, 0, 10, 192), Mask::new(26).unwrap()),
         ];
-        assert_eq!(expected, address.split(new_mask));
+        assert_eq!(expected, address.split(new_mask).collect::<Vec<_>>());
     }
 
     #[test]
     fn is_network_address() {
         let host_address = CIDR::new(IPv4::new(10, 0, 10, 15), Mask::new(24).unwrap());
-        assert_eq!(false, host_address.is_network_address());
+        assert!(!host_address.is_network_address());
 
         let network_address = CIDR::new(IPv4::new(10, 0, 10, 0), Mask::new(24).unwrap());
-        assert_eq!(true, network_address.is_network_address());
+        assert!(network_address.is_network_address());
     }
 
     #[test]
@@ -324,6 +722,42 @@ mod tests {
         let base_address = CIDR::new(IPv4::new(10, 0, 10, 12), Mask::new(32).unwrap());
         let compared = CIDR::new(IPv4::new(10, 0, 10, 13), Mask::new(32).unwrap());
         assert_eq!(CIDRComparison::Different, base_address.compare(&compared));
+        assert!(base_address.is_adjacent_to(&compared));
+
+        let compared = CIDR::new(IPv4::new(10, 0, 10, 14), Mask::new(32).unwrap());
+        assert_eq!(CIDRComparison::Different, base_address.compare(&compared));
+
+        let base_address = CIDR::new(IPv4::new(10, 0, 10, 12), Mask::new(31).unwrap());
+        let compared = CIDR::new(IPv4::new(10, 0, 10, 13), Mask::new(32).unwrap());
+        assert_eq!(CIDRComparison::Superset, base_address.compare(&compared));
+
+        let compared = CIDR::new(IPv4::new(10, 0, 10, 13), Mask::new(31).unwrap());
+        assert_eq!(CIDRComparison::Equals, base_address.compare(&compared));
+
+        let compared = CIDR::new(IPv4::new(10, 0, 10, 14), Mask::new(31).unwrap());
+        assert_eq!(CIDRComparison::Different, base_address.compare(&compared));
+        assert!(base_address.is_adjacent_to(&compared));
+
+        let compared = CIDR::new(IPv4::new(10, 0, 10, 16), Mask::new(31).unwrap());
+        assert_eq!(CIDRComparison::Different, base_address.compare(&compared));
+    }
+
+    #[test]
+    fn point_to_point_and_host_route() {
+        let link = "10.0.0.0/31".parse::<CIDR>().unwrap();
+        assert_eq!(Some(IPv4::new(10, 0, 0, 0)), link.first_address());
+        assert_eq!(Some(IPv4::new(10, 0, 0, 1)), link.last_address());
+        assert_eq!(None, link.broadcast_address());
+        assert_eq!(2, link.hosts());
+
+        let host = "10.0.0.1/32".parse::<CIDR>().unwrap();
+        assert_eq!(Some(IPv4::new(10, 0, 0, 1)), host.first_address());
+        assert_eq!(Some(IPv4::new(10, 0, 0, 1)), host.last_address());
+        assert_eq!(None, host.broadcast_address());
+        assert_eq!(1, host.hosts());
+
+        assert!(link.contains_cidr(&host));
+        assert!(host.contains_ip(&IPv4::new(10, 0, 0, 1)));
     }
 
     #[test]
@@ -351,4 +785,625 @@ mod tests {
             base_address.aggregate(Mask::new(28).unwrap())
         );
     }
+
+    #[test]
+    fn ipv6_parse_success() {
+        assert_eq!(
+            Ok(CIDR::new(
+                IPv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                Mask::new(64).unwrap()
+            )),
+            "2001:db8::1/64".parse::<CIDR<IPv6>>(),
+        );
+
+        assert_eq!(
+            Ok(CIDR::new(
+                IPv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1),
+                Mask::new(128).unwrap()
+            )),
+            "2001:db8::1".parse::<CIDR<IPv6>>(),
+        );
+
+        assert_eq!(
+            Err(CIDRParsingError::InvalidMaskLength),
+            "2001:db8::1/129".parse::<CIDR<IPv6>>(),
+        );
+    }
+
+    #[test]
+    fn ipv6_addresses() {
+        let address = "2001:db8::42/64".parse::<CIDR<IPv6>>().unwrap();
+
+        assert_eq!(
+            "2001:db8::/64".parse::<CIDR<IPv6>>().unwrap(),
+            address.network_address()
+        );
+        assert_eq!(
+            Some("2001:db8::".parse::<IPv6>().unwrap()),
+            address.first_address()
+        );
+        assert_eq!(
+            Some("2001:db8::ffff:ffff:ffff:ffff".parse::<IPv6>().unwrap()),
+            address.last_address()
+        );
+        assert_eq!(None, address.broadcast_address());
+        assert_eq!(1 << 64, address.hosts());
+        assert_eq!(None, address.class());
+        assert_eq!(IPKind::Special("documentation"), address.kind());
+    }
+
+    #[test]
+    fn ipv6_split() {
+        let address = "2001:db8::/62".parse::<CIDR<IPv6>>().unwrap();
+
+        let expected = vec![
+            "2001:db8::/64".parse::<CIDR<IPv6>>().unwrap(),
+            "2001:db8:0:1::/64".parse::<CIDR<IPv6>>().unwrap(),
+            "2001:db8:0:2::/64".parse::<CIDR<IPv6>>().unwrap(),
+            "2001:db8:0:3::/64".parse::<CIDR<IPv6>>().unwrap(),
+        ];
+        assert_eq!(
+            expected,
+            address.split(Mask::new(64).unwrap()).collect::<Vec<_>>()
+        );
+
+        let address = "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffc/126"
+            .parse::<CIDR<IPv6>>()
+            .unwrap();
+        assert_eq!(4, address.split(Mask::new(128).unwrap()).remaining());
+
+        let address = "::/0".parse::<CIDR<IPv6>>().unwrap();
+        let mut split = address.split(Mask::new(0).unwrap());
+        assert_eq!(1, split.remaining());
+        assert_eq!((1, Some(1)), split.size_hint());
+        assert_eq!(Some(address), split.next());
+        assert_eq!(0, split.remaining());
+    }
+
+    #[test]
+    fn ipv6_compare() {
+        let base_address = "2001:db8::/32".parse::<CIDR<IPv6>>().unwrap();
+
+        let compared = "2001:db8:1::/48".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(CIDRComparison::Superset, base_address.compare(&compared));
+
+        let compared = "2001::/16".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(CIDRComparison::Subset, base_address.compare(&compared));
+
+        let compared = "2001:db8::1/32".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(CIDRComparison::Equals, base_address.compare(&compared));
+
+        let compared = "2001:db9::/32".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(CIDRComparison::Different, base_address.compare(&compared));
+        assert!(base_address.is_adjacent_to(&compared));
+
+        let compared = "2001:dba::/32".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(CIDRComparison::Different, base_address.compare(&compared));
+    }
+
+    fn split_in_halves<A: AddressFamily>(raw: &str) -> Vec<String> {
+        let cidr = raw.parse::<CIDR<A>>().unwrap();
+        let mask = Mask::new(cidr.mask().prefix_length() + 1).unwrap();
+
+        cidr.split(mask).map(|c| c.to_string()).collect()
+    }
+
+    #[test]
+    fn generic_over_address_family() {
+        assert_eq!(
+            vec!["10.0.0.0/25", "10.0.0.128/25"],
+            split_in_halves::<IPv4>("10.0.0.0/24")
+        );
+        assert_eq!(
+            vec!["2001:db8::/33", "2001:db8:8000::/33"],
+            split_in_halves::<IPv6>("2001:db8::/32")
+        );
+    }
+
+    #[test]
+    fn special_purposes() {
+        let cidr = "172.0.0.0/8".parse::<CIDR>().unwrap();
+        let overlaps = cidr.special_purposes();
+        assert_eq!(1, overlaps.len());
+        assert_eq!(CIDRComparison::Superset, overlaps[0].relation);
+        assert_eq!(
+            "172.16.0.0/12".parse::<CIDR>().unwrap(),
+            overlaps[0].block.network::<IPv4>()
+        );
+
+        let cidr = "10.12.0.0/16".parse::<CIDR>().unwrap();
+        let overlaps = cidr.special_purposes();
+        assert_eq!(1, overlaps.len());
+        assert_eq!(CIDRComparison::Subset, overlaps[0].relation);
+        assert_eq!("Private-Use", overlaps[0].block.name);
+
+        let cidr = "192.0.0.0/29".parse::<CIDR>().unwrap();
+        let overlaps = cidr
+            .special_purposes()
+            .iter()
+            .map(|overlap| {
+                (
+                    overlap.block.network::<IPv4>().to_string(),
+                    overlap.relation,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("192.0.0.0/24".to_string(), CIDRComparison::Subset),
+                ("192.0.0.0/29".to_string(), CIDRComparison::Equals),
+            ],
+            overlaps
+        );
+
+        let cidr = "8.8.0.0/16".parse::<CIDR>().unwrap();
+        assert!(cidr.special_purposes().is_empty());
+
+        let cidr = "2000::/3".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(15, cidr.special_purposes().len());
+    }
+
+    #[test]
+    fn has_public_remainder() {
+        let cidr = "172.0.0.0/8".parse::<CIDR>().unwrap();
+        assert!(cidr.has_public_remainder());
+
+        let cidr = "10.12.0.0/16".parse::<CIDR>().unwrap();
+        assert!(!cidr.has_public_remainder());
+
+        let cidr = "8.8.0.0/16".parse::<CIDR>().unwrap();
+        assert!(cidr.has_public_remainder());
+
+        let cidr = "192.168.0.0/15".parse::<CIDR>().unwrap();
+        assert!(cidr.has_public_remainder());
+
+        let cidr = "240.0.0.0/4".parse::<CIDR>().unwrap();
+        assert!(!cidr.has_public_remainder());
+
+        let cidr = "198.18.0.0/15".parse::<CIDR>().unwrap();
+        assert!(!cidr.has_public_remainder());
+
+        let cidr = "fc00::/6".parse::<CIDR<IPv6>>().unwrap();
+        assert!(cidr.has_public_remainder());
+
+        let cidr = "fe00::/7".parse::<CIDR<IPv6>>().unwrap();
+        assert!(cidr.has_public_remainder());
+    }
+
+    #[test]
+    fn contains_ip() {
+        let cidr = "10.0.10.0/24".parse::<CIDR>().unwrap();
+        assert!(cidr.contains_ip(&IPv4::new(10, 0, 10, 0)));
+        assert!(cidr.contains_ip(&IPv4::new(10, 0, 10, 42)));
+        assert!(cidr.contains_ip(&IPv4::new(10, 0, 10, 255)));
+        assert!(!cidr.contains_ip(&IPv4::new(10, 0, 11, 0)));
+        assert!(!cidr.contains_ip(&IPv4::new(10, 0, 9, 255)));
+
+        let cidr = "10.0.10.4/31".parse::<CIDR>().unwrap();
+        assert!(cidr.contains_ip(&IPv4::new(10, 0, 10, 4)));
+        assert!(cidr.contains_ip(&IPv4::new(10, 0, 10, 5)));
+        assert!(!cidr.contains_ip(&IPv4::new(10, 0, 10, 6)));
+
+        let cidr = "10.0.10.4/32".parse::<CIDR>().unwrap();
+        assert!(cidr.contains_ip(&IPv4::new(10, 0, 10, 4)));
+        assert!(!cidr.contains_ip(&IPv4::new(10, 0, 10, 5)));
+
+        let cidr = "0.0.0.0/0".parse::<CIDR>().unwrap();
+        assert!(cidr.contains_ip(&IPv4::new(255, 255, 255, 255)));
+
+        let cidr = "2001:db8::/32".parse::<CIDR<IPv6>>().unwrap();
+        assert!(cidr.contains_ip(&"2001:db8:ffff::1".parse::<IPv6>().unwrap()));
+        assert!(!cidr.contains_ip(&"2001:db9::".parse::<IPv6>().unwrap()));
+    }
+
+    #[test]
+    fn contains_cidr() {
+        let cidr = "10.0.10.0/24".parse::<CIDR>().unwrap();
+        assert!(cidr.contains_cidr(&"10.0.10.0/24".parse::<CIDR>().unwrap()));
+        assert!(cidr.contains_cidr(&"10.0.10.128/25".parse::<CIDR>().unwrap()));
+        assert!(cidr.contains_cidr(&"10.0.10.254/31".parse::<CIDR>().unwrap()));
+        assert!(cidr.contains_cidr(&"10.0.10.255/32".parse::<CIDR>().unwrap()));
+        assert!(!cidr.contains_cidr(&"10.0.0.0/16".parse::<CIDR>().unwrap()));
+        assert!(!cidr.contains_cidr(&"10.0.11.0/32".parse::<CIDR>().unwrap()));
+
+        let cidr = "10.0.10.4/32".parse::<CIDR>().unwrap();
+        assert!(cidr.contains_cidr(&"10.0.10.4/32".parse::<CIDR>().unwrap()));
+        assert!(!cidr.contains_cidr(&"10.0.10.4/31".parse::<CIDR>().unwrap()));
+    }
+
+    #[test]
+    fn overlaps() {
+        let cidr = "10.0.10.0/24".parse::<CIDR>().unwrap();
+        assert!(cidr.overlaps(&"10.0.0.0/16".parse::<CIDR>().unwrap()));
+        assert!(cidr.overlaps(&"10.0.10.64/26".parse::<CIDR>().unwrap()));
+        assert!(cidr.overlaps(&"10.0.10.255/32".parse::<CIDR>().unwrap()));
+        assert!(!cidr.overlaps(&"10.0.11.0/24".parse::<CIDR>().unwrap()));
+
+        let cidr = "10.0.10.4/31".parse::<CIDR>().unwrap();
+        assert!(cidr.overlaps(&"10.0.10.5/32".parse::<CIDR>().unwrap()));
+        assert!(!cidr.overlaps(&"10.0.10.6/31".parse::<CIDR>().unwrap()));
+    }
+
+    #[test]
+    fn is_adjacent_to() {
+        let cidr = "10.0.10.0/24".parse::<CIDR>().unwrap();
+        assert!(cidr.is_adjacent_to(&"10.0.11.0/24".parse::<CIDR>().unwrap()));
+        assert!(cidr.is_adjacent_to(&"10.0.9.255/32".parse::<CIDR>().unwrap()));
+        assert!(cidr.is_adjacent_to(&"10.0.8.0/23".parse::<CIDR>().unwrap()));
+        assert!(!cidr.is_adjacent_to(&"10.0.12.0/24".parse::<CIDR>().unwrap()));
+        assert!(!cidr.is_adjacent_to(&"10.0.10.0/25".parse::<CIDR>().unwrap()));
+
+        let cidr = "10.0.10.4/32".parse::<CIDR>().unwrap();
+        assert!(cidr.is_adjacent_to(&"10.0.10.5/32".parse::<CIDR>().unwrap()));
+        assert!(cidr.is_adjacent_to(&"10.0.10.2/31".parse::<CIDR>().unwrap()));
+
+        let cidr = "255.255.255.255/32".parse::<CIDR>().unwrap();
+        assert!(!cidr.is_adjacent_to(&"0.0.0.0/32".parse::<CIDR>().unwrap()));
+    }
+
+    #[test]
+    fn intersection() {
+        let cidr = "10.0.10.15/24".parse::<CIDR>().unwrap();
+        assert_eq!(
+            Some("10.0.10.64/26".parse::<CIDR>().unwrap()),
+            cidr.intersection(&"10.0.10.77/26".parse::<CIDR>().unwrap())
+        );
+        assert_eq!(
+            Some("10.0.10.0/24".parse::<CIDR>().unwrap()),
+            cidr.intersection(&"10.0.0.0/16".parse::<CIDR>().unwrap())
+        );
+        assert_eq!(
+            Some("10.0.10.5/32".parse::<CIDR>().unwrap()),
+            cidr.intersection(&"10.0.10.5/32".parse::<CIDR>().unwrap())
+        );
+        assert_eq!(
+            None,
+            cidr.intersection(&"10.0.11.0/24".parse::<CIDR>().unwrap())
+        );
+
+        let cidr = "10.0.10.4/31".parse::<CIDR>().unwrap();
+        assert_eq!(
+            Some("10.0.10.4/31".parse::<CIDR>().unwrap()),
+            cidr.intersection(&"10.0.10.4/31".parse::<CIDR>().unwrap())
+        );
+    }
+
+    #[test]
+    fn common_supernet() {
+        let cidr = "10.0.10.15/24".parse::<CIDR>().unwrap();
+        assert_eq!(
+            "10.0.10.0/23".parse::<CIDR>().unwrap(),
+            cidr.common_supernet(&"10.0.11.0/24".parse::<CIDR>().unwrap())
+        );
+        assert_eq!(
+            "10.0.8.0/21".parse::<CIDR>().unwrap(),
+            cidr.common_supernet(&"10.0.12.0/24".parse::<CIDR>().unwrap())
+        );
+        assert_eq!(
+            "10.0.0.0/16".parse::<CIDR>().unwrap(),
+            cidr.common_supernet(&"10.0.0.0/16".parse::<CIDR>().unwrap())
+        );
+        assert_eq!(
+            "0.0.0.0/0".parse::<CIDR>().unwrap(),
+            cidr.common_supernet(&"192.168.0.0/16".parse::<CIDR>().unwrap())
+        );
+
+        let cidr = "2001:db8::/48".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(
+            "2001:db8::/46".parse::<CIDR<IPv6>>().unwrap(),
+            cidr.common_supernet(&"2001:db8:2::/48".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn merge() {
+        let cidr = "10.0.10.15/24".parse::<CIDR>().unwrap();
+        assert_eq!(
+            Some("10.0.10.0/23".parse::<CIDR>().unwrap()),
+            cidr.merge(&"10.0.11.0/24".parse::<CIDR>().unwrap())
+        );
+        // adjacent, but not the two halves of a /23
+        assert_eq!(None, cidr.merge(&"10.0.9.0/24".parse::<CIDR>().unwrap()));
+        assert_eq!(None, cidr.merge(&"10.0.11.0/25".parse::<CIDR>().unwrap()));
+        assert_eq!(None, cidr.merge(&"10.0.10.0/24".parse::<CIDR>().unwrap()));
+
+        let cidr = "0.0.0.0/1".parse::<CIDR>().unwrap();
+        assert_eq!(
+            Some("0.0.0.0/0".parse::<CIDR>().unwrap()),
+            cidr.merge(&"128.0.0.0/1".parse::<CIDR>().unwrap())
+        );
+    }
+
+    #[test]
+    fn gap() {
+        let cidr = "10.0.10.15/24".parse::<CIDR>().unwrap();
+        assert_eq!(0, cidr.gap(&"10.0.11.0/24".parse::<CIDR>().unwrap()));
+        assert_eq!(0, cidr.gap(&"10.0.0.0/16".parse::<CIDR>().unwrap()));
+        assert_eq!(256, cidr.gap(&"10.0.12.0/24".parse::<CIDR>().unwrap()));
+        assert_eq!(256, cidr.gap(&"10.0.8.0/24".parse::<CIDR>().unwrap()));
+
+        let cidr = "::/128".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(
+            u128::MAX - 1,
+            cidr.gap(
+                &"ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"
+                    .parse()
+                    .unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn exclude() {
+        let network = "10.0.0.0/16".parse::<CIDR>().unwrap();
+        let excluded = vec![
+            "10.0.4.0/22".parse::<CIDR>().unwrap(),
+            "10.0.200.0/24".parse::<CIDR>().unwrap(),
+        ];
+        let expected: Vec<CIDR> = [
+            "10.0.0.0/22",
+            "10.0.8.0/21",
+            "10.0.16.0/20",
+            "10.0.32.0/19",
+            "10.0.64.0/18",
+            "10.0.128.0/18",
+            "10.0.192.0/21",
+            "10.0.201.0/24",
+            "10.0.202.0/23",
+            "10.0.204.0/22",
+            "10.0.208.0/20",
+            "10.0.224.0/19",
+        ]
+        .iter()
+        .map(|cidr| cidr.parse().unwrap())
+        .collect();
+
+        assert_eq!(expected, network.exclude(&excluded));
+    }
+
+    #[test]
+    fn exclude_edge_cases() {
+        let network = "10.0.0.0/24".parse::<CIDR>().unwrap();
+
+        assert_eq!(
+            vec!["10.0.0.0/24".parse::<CIDR>().unwrap()],
+            network.exclude(&[])
+        );
+        assert_eq!(
+            vec!["10.0.0.0/24".parse::<CIDR>().unwrap()],
+            network.exclude(&["192.168.0.0/16".parse().unwrap()])
+        );
+        assert_eq!(
+            Vec::<CIDR>::new(),
+            network.exclude(&["10.0.0.0/8".parse().unwrap()])
+        );
+        assert_eq!(
+            vec![
+                "10.0.0.0/25".parse::<CIDR>().unwrap(),
+                "10.0.0.128/26".parse::<CIDR>().unwrap(),
+                "10.0.0.192/27".parse::<CIDR>().unwrap(),
+                "10.0.0.224/28".parse::<CIDR>().unwrap(),
+                "10.0.0.240/29".parse::<CIDR>().unwrap(),
+                "10.0.0.248/30".parse::<CIDR>().unwrap(),
+                "10.0.0.252/31".parse::<CIDR>().unwrap(),
+                "10.0.0.254/32".parse::<CIDR>().unwrap(),
+            ],
+            network.exclude(&["10.0.0.255/32".parse().unwrap()])
+        );
+
+        let network = "::/0".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(
+            vec![
+                "::/1".parse::<CIDR<IPv6>>().unwrap(),
+                "c000::/2".parse::<CIDR<IPv6>>().unwrap(),
+            ],
+            network.exclude(&["8000::/2".parse().unwrap()])
+        );
+    }
+
+    #[test]
+    fn split_is_lazy() {
+        let address = "0.0.0.0/0".parse::<CIDR>().unwrap();
+        let mut subnets = address.split(Mask::new(32).unwrap());
+
+        assert_eq!(1 << 32, subnets.len());
+        assert_eq!(Some("0.0.0.0/32".parse().unwrap()), subnets.next());
+        assert_eq!(
+            Some("255.255.255.255/32".parse().unwrap()),
+            subnets.next_back()
+        );
+        assert_eq!((1 << 32) - 2, subnets.len());
+
+        let address = "::/0".parse::<CIDR<IPv6>>().unwrap();
+        let mut subnets = address.split(Mask::new(65).unwrap());
+        assert_eq!(1 << 65, subnets.remaining());
+        assert_eq!((usize::MAX, None), subnets.size_hint());
+        assert_eq!(Some("::/65".parse().unwrap()), subnets.next());
+        assert_eq!((1 << 65) - 1, subnets.remaining());
+    }
+
+    #[test]
+    fn split_top_of_address_space() {
+        let address = "255.255.255.252/30".parse::<CIDR>().unwrap();
+
+        let expected: Vec<CIDR> = [
+            "255.255.255.252/32",
+            "255.255.255.253/32",
+            "255.255.255.254/32",
+            "255.255.255.255/32",
+        ]
+        .iter()
+        .map(|cidr| cidr.parse().unwrap())
+        .collect();
+        assert_eq!(
+            expected,
+            address.split(Mask::new(32).unwrap()).collect::<Vec<_>>()
+        );
+
+        let address = "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffc/126"
+            .parse::<CIDR<IPv6>>()
+            .unwrap();
+        let subnets: Vec<_> = address.split(Mask::new(128).unwrap()).collect();
+        assert_eq!(4, subnets.len());
+        assert_eq!(
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"
+                .parse::<CIDR<IPv6>>()
+                .unwrap(),
+            subnets[3]
+        );
+    }
+
+    #[test]
+    fn split_double_ended() {
+        let address = "10.0.0.0/24".parse::<CIDR>().unwrap();
+        let mut subnets = address.split(Mask::new(26).unwrap());
+
+        assert_eq!(4, subnets.len());
+        assert_eq!(Some("10.0.0.192/26".parse().unwrap()), subnets.next_back());
+        assert_eq!(Some("10.0.0.0/26".parse().unwrap()), subnets.next());
+        assert_eq!(Some("10.0.0.128/26".parse().unwrap()), subnets.next_back());
+        assert_eq!(Some("10.0.0.64/26".parse().unwrap()), subnets.next());
+        assert_eq!(0, subnets.len());
+        assert_eq!(None, subnets.next());
+        assert_eq!(None, subnets.next_back());
+
+        let reversed: Vec<String> = address
+            .split(Mask::new(25).unwrap())
+            .rev()
+            .map(|cidr| cidr.to_string())
+            .collect();
+        assert_eq!(vec!["10.0.0.128/25", "10.0.0.0/25"], reversed);
+    }
+
+    fn strings<A: AddressFamily>(addresses: impl Iterator<Item = A>) -> Vec<String> {
+        addresses.map(|address| address.to_string()).collect()
+    }
+
+    #[test]
+    fn addresses() {
+        let network = "10.0.0.5/30".parse::<CIDR>().unwrap();
+
+        assert_eq!(
+            vec!["10.0.0.4", "10.0.0.5", "10.0.0.6", "10.0.0.7"],
+            strings(network.addresses())
+        );
+        assert_eq!(
+            vec!["10.0.0.7", "10.0.0.6", "10.0.0.5", "10.0.0.4"],
+            strings(network.addresses().rev())
+        );
+        assert_eq!(4, network.addresses().len());
+
+        let network = "::/1".parse::<CIDR<IPv6>>().unwrap();
+        let mut addresses = network.addresses();
+        assert_eq!(1 << 127, addresses.remaining());
+        assert_eq!((usize::MAX, None), addresses.size_hint());
+        assert_eq!(Some("::".parse().unwrap()), addresses.next());
+        assert_eq!((1 << 127) - 1, addresses.remaining());
+    }
+
+    #[test]
+    fn host_addresses() {
+        let network = "10.0.0.0/29".parse::<CIDR>().unwrap();
+        assert_eq!(
+            vec!["10.0.0.1", "10.0.0.2", "10.0.0.3", "10.0.0.4", "10.0.0.5", "10.0.0.6"],
+            strings(network.host_addresses())
+        );
+
+        let link = "10.0.0.0/31".parse::<CIDR>().unwrap();
+        assert_eq!(vec!["10.0.0.0", "10.0.0.1"], strings(link.host_addresses()));
+
+        let host = "10.0.0.1/32".parse::<CIDR>().unwrap();
+        assert_eq!(vec!["10.0.0.1"], strings(host.host_addresses()));
+
+        let network = "2001:db8::/126".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!(
+            vec!["2001:db8::", "2001:db8::1", "2001:db8::2", "2001:db8::3"],
+            strings(network.host_addresses())
+        );
+    }
+
+    #[test]
+    fn addresses_stride() {
+        let network = "10.0.0.0/24".parse::<CIDR>().unwrap();
+
+        assert_eq!(
+            vec!["10.0.0.1", "10.0.0.65", "10.0.0.129", "10.0.0.193"],
+            strings(network.host_addresses().step_by(64))
+        );
+        assert_eq!(
+            vec!["10.0.0.254", "10.0.0.154", "10.0.0.54"],
+            strings(network.host_addresses().rev().step_by(100))
+        );
+        assert_eq!(
+            vec!["10.0.0.250", "10.0.0.251"],
+            strings(network.addresses().skip(250).take(2))
+        );
+        assert_eq!(None, network.addresses().nth(256));
+        assert_eq!(None, network.addresses().nth_back(256));
+    }
+
+    #[test]
+    fn addresses_large_networks() {
+        let network = "0.0.0.0/0".parse::<CIDR>().unwrap();
+        assert_eq!(
+            Some(IPv4::new(255, 255, 255, 255)),
+            network.addresses().next_back()
+        );
+        assert_eq!(
+            Some(IPv4::new(128, 0, 0, 0)),
+            network.addresses().nth(1 << 31)
+        );
+
+        let network = "::/0".parse::<CIDR<IPv6>>().unwrap();
+        let addresses = network.addresses();
+        assert_eq!(u128::MAX, addresses.remaining());
+        assert_eq!(
+            vec![
+                "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+                "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe"
+            ],
+            strings(addresses.rev().take(2))
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let cidr = "10.12.23.43/20".parse::<CIDR>().unwrap();
+        assert_eq!("\"10.12.23.43/20\"", serde_json::to_string(&cidr).unwrap());
+
+        let cidr = "2001:db8::/32".parse::<CIDR<IPv6>>().unwrap();
+        assert_eq!("\"2001:db8::/32\"", serde_json::to_string(&cidr).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        let cidrs: Vec<CIDR> =
+            serde_json::from_str(r#"["10.12.23.43/20", "10.0.0.0/255.255.0.0"]"#).unwrap();
+        assert_eq!(
+            vec![
+                "10.12.23.43/20".parse::<CIDR>().unwrap(),
+                "10.0.0.0/16".parse::<CIDR>().unwrap()
+            ],
+            cidrs
+        );
+
+        let cidr: CIDR<IPv6> = serde_json::from_str("\"2001:db8::/32\"").unwrap();
+        assert_eq!("2001:db8::/32".parse::<CIDR<IPv6>>().unwrap(), cidr);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_errors() {
+        let error = |raw| serde_json::from_str::<CIDR>(raw).unwrap_err().to_string();
+
+        assert_eq!("invalid CIDR format", error("\"10.12.23/20\""));
+        assert_eq!("invalid mask length", error("\"10.12.23.43/33\""));
+        assert_eq!(
+            "mask must be contiguous but bit 25 breaks contiguity",
+            error("\"10.12.23.43/255.255.0.255\"")
+        );
+        assert!(error("42").starts_with("invalid type: integer `42`"));
+    }
 }
@@ -0,0 +1,183 @@
+use crate::net::AddressFamily;
+use crate::net::IPSet;
+use crate::net::IPv4;
+use crate::net::Mask;
+use crate::net::CIDR;
+
+/// Addresses of a parent network not covered by any of the used prefixes.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FreeSpace<A: AddressFamily = IPv4> {
+    parent: CIDR<A>,
+    free: IPSet<A>,
+    outside: Vec<CIDR<A>>,
+}
+
+impl<A: AddressFamily> FreeSpace<A> {
+    pub fn new(parent: &CIDR<A>, used: &[CIDR<A>]) -> Self {
+        let parent = parent.network_address();
+        let used_set = used.iter().copied().collect::<IPSet<A>>();
+        let outside = used
+            .iter()
+            .filter(|cidr| !parent.contains_cidr(cidr))
+            .copied()
+            .collect();
+
+        Self {
+            parent,
+            free: IPSet::from(parent).difference(&used_set),
+            outside,
+        }
+    }
+
+    pub fn parent(&self) -> CIDR<A> {
+        self.parent
+    }
+
+    /// Used prefixes that aren't entirely within the parent network, in the
+    /// order they were given.
+    pub fn outside(&self) -> &[CIDR<A>] {
+        &self.outside
+    }
+
+    pub fn addresses(&self) -> u128 {
+        self.free.addresses()
+    }
+
+    /// Largest aligned free networks, in ascending order.
+    pub fn cidrs(&self) -> impl Iterator<Item = CIDR<A>> + '_ {
+        self.free.cidrs()
+    }
+
+    /// Every free network using the given mask, in ascending order.
+    pub fn blocks(&self, mask: Mask<A>) -> impl Iterator<Item = CIDR<A>> + '_ {
+        self.cidrs()
+            .filter(move |cidr| cidr.mask() <= mask)
+            .flat_map(move |cidr| cidr.split(mask))
+    }
+
+    /// Number of free networks per mask, from the largest networks to the
+    /// smallest.
+    pub fn counts(&self) -> Vec<(Mask<A>, usize)> {
+        let mut counts = vec![0; A::BITS as usize + 1];
+        for cidr in self.cidrs() {
+            counts[cidr.mask().prefix_length() as usize] += 1;
+        }
+
+        counts
+            .into_iter()
+            .enumerate()
+            .filter(|(_, count)| *count > 0)
+            .map(|(prefix_length, count)| (Mask::new(prefix_length as u8).unwrap(), count))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FreeSpace;
+    use crate::net::{cidrs, IPv4, IPv6, Mask, CIDR};
+
+    #[test]
+    fn free_space_nothing_used() {
+        let parent = "10.20.0.0/16".parse::<CIDR>().unwrap();
+        let free = FreeSpace::new(&parent, &[]);
+
+        assert_eq!(
+            cidrs::<IPv4>(&["10.20.0.0/16"]),
+            free.cidrs().collect::<Vec<_>>()
+        );
+        assert_eq!(65536, free.addresses());
+        assert!(free.outside().is_empty());
+    }
+
+    #[test]
+    fn free_space_largest_aligned_blocks() {
+        let parent = "10.20.0.0/16".parse::<CIDR>().unwrap();
+        let used = cidrs::<IPv4>(&["10.20.0.0/24", "10.20.2.0/23", "10.20.128.0/17"]);
+        let free = FreeSpace::new(&parent, &used);
+
+        assert_eq!(
+            cidrs::<IPv4>(&[
+                "10.20.1.0/24",
+                "10.20.4.0/22",
+                "10.20.8.0/21",
+                "10.20.16.0/20",
+                "10.20.32.0/19",
+                "10.20.64.0/18",
+            ]),
+            free.cidrs().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                (Mask::new(18).unwrap(), 1),
+                (Mask::new(19).unwrap(), 1),
+                (Mask::new(20).unwrap(), 1),
+                (Mask::new(21).unwrap(), 1),
+                (Mask::new(22).unwrap(), 1),
+                (Mask::new(24).unwrap(), 1),
+            ],
+            free.counts()
+        );
+    }
+
+    #[test]
+    fn free_space_blocks() {
+        let parent = "10.20.0.0/22".parse::<CIDR>().unwrap();
+        let used = cidrs::<IPv4>(&["10.20.0.0/24", "10.20.3.128/25"]);
+        let free = FreeSpace::new(&parent, &used);
+
+        assert_eq!(
+            cidrs::<IPv4>(&["10.20.1.0/24", "10.20.2.0/24"]),
+            free.blocks(Mask::new(24).unwrap()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            cidrs::<IPv4>(&[
+                "10.20.1.0/25",
+                "10.20.1.128/25",
+                "10.20.2.0/25",
+                "10.20.2.128/25",
+                "10.20.3.0/25"
+            ]),
+            free.blocks(Mask::new(25).unwrap()).collect::<Vec<_>>()
+        );
+        assert_eq!(0, free.blocks(Mask::new(22).unwrap()).count());
+    }
+
+    #[test]
+    fn free_space_outside() {
+        let parent = "10.20.0.0/16".parse::<CIDR>().unwrap();
+        let used = cidrs::<IPv4>(&["10.21.0.0/24", "10.20.5.0/24", "10.0.0.0/8"]);
+        let free = FreeSpace::new(&parent, &used);
+
+        assert_eq!(
+            cidrs::<IPv4>(&["10.21.0.0/24", "10.0.0.0/8"]),
+            free.outside()
+        );
+        assert_eq!(0, free.addresses());
+    }
+
+    #[test]
+    fn free_space_host_parent() {
+        let parent = "10.20.30.40/24".parse::<CIDR>().unwrap();
+        let free = FreeSpace::new(&parent, &cidrs::<IPv4>(&["10.20.30.0/25"]));
+
+        assert_eq!("10.20.30.0/24", free.parent().to_string());
+        assert_eq!(
+            cidrs::<IPv4>(&["10.20.30.128/25"]),
+            free.cidrs().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn free_space_ipv6() {
+        let parent = "2001:db8::/32".parse::<CIDR<IPv6>>().unwrap();
+        let used = cidrs::<IPv6>(&["2001:db8::/33"]);
+        let free = FreeSpace::new(&parent, &used);
+
+        assert_eq!(
+            cidrs::<IPv6>(&["2001:db8:8000::/33"]),
+            free.cidrs().collect::<Vec<_>>()
+        );
+        assert_eq!(1 << 95, free.addresses());
+    }
+}
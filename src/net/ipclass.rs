@@ -23,6 +23,32 @@ impl fmt::Display for IPClass {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for IPClass {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IPClass {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+
+        match raw.as_str() {
+            "A" => Ok(IPClass::A),
+            "B" => Ok(IPClass::B),
+            "C" => Ok(IPClass::C),
+            "D" => Ok(IPClass::D),
+            "E" => Ok(IPClass::E),
+            _ => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&raw),
+                &"one of A, B, C, D or E",
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -49,4 +75,27 @@ mod tests {
     fn class_e() {
         assert_eq!("E", format!("{}", super::IPClass::E))
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        assert_eq!("\"B\"", serde_json::to_string(&super::IPClass::B).unwrap())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use super::IPClass;
+
+        for class in [IPClass::A, IPClass::B, IPClass::C, IPClass::D, IPClass::E] {
+            let raw = serde_json::to_string(&class).unwrap();
+            assert_eq!(class, serde_json::from_str::<IPClass>(&raw).unwrap());
+        }
+        assert_eq!(
+            "invalid value: string \"F\", expected one of A, B, C, D or E",
+            serde_json::from_str::<IPClass>("\"F\"")
+                .unwrap_err()
+                .to_string()
+        );
+    }
 }
@@ -17,6 +17,43 @@ impl fmt::Display for IPKind {
     }
 }
 
+#[cfg(feature = "serde")]
+impl serde::Serialize for IPKind {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// The special kinds are the labels of the special-purpose registries, as
+/// `Special` only holds static strings.
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IPKind {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use crate::net::{IPV4_SPECIAL_PURPOSE, IPV6_SPECIAL_PURPOSE};
+
+        let raw = String::deserialize(deserializer)?;
+        let special = raw
+            .strip_prefix("Special (")
+            .and_then(|kind| kind.strip_suffix(')'))
+            .and_then(|kind| {
+                IPV4_SPECIAL_PURPOSE
+                    .iter()
+                    .chain(IPV6_SPECIAL_PURPOSE.iter())
+                    .find(|block| block.label == kind)
+            });
+
+        match (raw.as_str(), special) {
+            ("Private Internet", _) => Ok(IPKind::Private),
+            ("Public Internet", _) => Ok(IPKind::Public),
+            (_, Some(block)) => Ok(IPKind::Special(block.label)),
+            _ => Err(serde::de::Error::invalid_value(
+                serde::de::Unexpected::Str(&raw),
+                &"Private Internet, Public Internet or Special (kind) of a special-purpose block",
+            )),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     #[test]
@@ -36,4 +73,35 @@ mod tests {
             format!("{}", super::IPKind::Special("something"))
         )
     }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            "\"Special (localhost)\"",
+            serde_json::to_string(&super::IPKind::Special("localhost")).unwrap()
+        )
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        use super::IPKind;
+
+        for kind in [
+            IPKind::Private,
+            IPKind::Public,
+            IPKind::Special("localhost"),
+            IPKind::Special("documentation"),
+            IPKind::Special("Benchmarking"),
+            IPKind::Special("Discard-Only Address Block"),
+        ] {
+            let raw = serde_json::to_string(&kind).unwrap();
+            assert_eq!(kind, serde_json::from_str::<IPKind>(&raw).unwrap());
+        }
+        assert!(serde_json::from_str::<IPKind>("\"Special (something)\"")
+            .unwrap_err()
+            .to_string()
+            .starts_with("invalid value: string \"Special (something)\""));
+    }
 }
@@ -0,0 +1,236 @@
+use crate::net::AddressFamily;
+use crate::net::IPv4;
+use crate::net::Mask;
+use crate::net::CIDR;
+use core::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum IPRangeParsingError {
+    InvalidFormat,
+    InvalidAddressFormat,
+    ReversedBounds,
+}
+
+/// Inclusive range of addresses, not necessarily aligned on a network
+/// boundary, such as `10.0.0.5-10.0.1.17`.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct IPRange<A: AddressFamily = IPv4> {
+    first: A,
+    last: A,
+}
+
+impl<A: AddressFamily> fmt::Display for IPRange<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", self.first, self.last)
+    }
+}
+
+impl<A: AddressFamily> std::str::FromStr for IPRange<A> {
+    type Err = IPRangeParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, last) = s
+            .split_once('-')
+            .ok_or(IPRangeParsingError::InvalidFormat)?;
+
+        let first = first
+            .trim()
+            .parse::<A>()
+            .map_err(|_| IPRangeParsingError::InvalidAddressFormat)?;
+        let last = last
+            .trim()
+            .parse::<A>()
+            .map_err(|_| IPRangeParsingError::InvalidAddressFormat)?;
+
+        Self::new(first, last)
+    }
+}
+
+impl<A: AddressFamily> From<CIDR<A>> for IPRange<A> {
+    fn from(cidr: CIDR<A>) -> Self {
+        let first = cidr.network_address().ip();
+        let last = A::from_u128(first.to_u128() | cidr.mask().wildcard().bits());
+
+        Self { first, last }
+    }
+}
+
+impl<A: AddressFamily> IPRange<A> {
+    pub fn new(first: A, last: A) -> Result<Self, IPRangeParsingError> {
+        if last < first {
+            return Err(IPRangeParsingError::ReversedBounds);
+        }
+
+        Ok(Self { first, last })
+    }
+
+    pub fn first(&self) -> A {
+        self.first
+    }
+
+    pub fn last(&self) -> A {
+        self.last
+    }
+
+    /// Number of addresses in the range, saturating at `u128::MAX`.
+    pub fn addresses(&self) -> u128 {
+        (self.last.to_u128() - self.first.to_u128()).saturating_add(1)
+    }
+
+    /// Smallest set of CIDRs covering exactly the range, in ascending order.
+    /// Each network is the largest one aligned on the next uncovered address
+    /// that doesn't go past the end of the range.
+    pub fn cidrs(&self) -> impl Iterator<Item = CIDR<A>> {
+        let last = self.last.to_u128();
+        let mut next = Some(self.first.to_u128());
+
+        std::iter::from_fn(move || {
+            let start = next?;
+            let alignment = start.trailing_zeros().min(A::BITS as u32);
+            let span = (last - start).checked_add(1).map_or(128, u128::ilog2);
+            let host_bits = alignment.min(span);
+
+            let end = start | (u128::MAX.checked_shr(128 - host_bits).unwrap_or(0));
+            next = if end < last { Some(end + 1) } else { None };
+
+            let mask = Mask::new(A::BITS - host_bits as u8).unwrap();
+            Some(CIDR::new(A::from_u128(start), mask))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IPRange, IPRangeParsingError};
+    use crate::net::{cidrs, IPv4, IPv6, CIDR};
+
+    #[test]
+    fn parse_success() {
+        let expected = IPRange::new(IPv4::new(10, 0, 0, 5), IPv4::new(10, 0, 1, 17)).unwrap();
+
+        assert_eq!(Ok(expected), "10.0.0.5-10.0.1.17".parse::<IPRange>());
+        assert_eq!(Ok(expected), "10.0.0.5 - 10.0.1.17".parse::<IPRange>());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err(IPRangeParsingError::InvalidFormat),
+            "10.0.0.5".parse::<IPRange>()
+        );
+        assert_eq!(
+            Err(IPRangeParsingError::InvalidAddressFormat),
+            "10.0.0.5-10.0.1".parse::<IPRange>()
+        );
+        assert_eq!(
+            Err(IPRangeParsingError::InvalidAddressFormat),
+            "10.0.0.5-10.0.1.17-10.0.2.0".parse::<IPRange>()
+        );
+        assert_eq!(
+            Err(IPRangeParsingError::ReversedBounds),
+            "10.0.1.17-10.0.0.5".parse::<IPRange>()
+        );
+    }
+
+    #[test]
+    fn string_display() {
+        let range = "10.0.0.5 - 10.0.1.17".parse::<IPRange>().unwrap();
+        assert_eq!("10.0.0.5-10.0.1.17", format!("{}", range));
+    }
+
+    #[test]
+    fn from_cidr() {
+        let range = IPRange::from("10.0.10.15/24".parse::<CIDR>().unwrap());
+        assert_eq!(IPv4::new(10, 0, 10, 0), range.first());
+        assert_eq!(IPv4::new(10, 0, 10, 255), range.last());
+        assert_eq!(256, range.addresses());
+
+        let range = IPRange::from("10.0.10.15/32".parse::<CIDR>().unwrap());
+        assert_eq!(IPv4::new(10, 0, 10, 15), range.first());
+        assert_eq!(IPv4::new(10, 0, 10, 15), range.last());
+    }
+
+    #[test]
+    fn addresses() {
+        let range = "10.0.0.5-10.0.1.17".parse::<IPRange>().unwrap();
+        assert_eq!(269, range.addresses());
+
+        let range = ":: - ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"
+            .parse::<IPRange<IPv6>>()
+            .unwrap();
+        assert_eq!(u128::MAX, range.addresses());
+    }
+
+    #[test]
+    fn cidrs_unaligned() {
+        let range = "10.0.0.5-10.0.1.17".parse::<IPRange>().unwrap();
+        let expected = cidrs::<IPv4>(&[
+            "10.0.0.5/32",
+            "10.0.0.6/31",
+            "10.0.0.8/29",
+            "10.0.0.16/28",
+            "10.0.0.32/27",
+            "10.0.0.64/26",
+            "10.0.0.128/25",
+            "10.0.1.0/28",
+            "10.0.1.16/31",
+        ]);
+
+        assert_eq!(expected, range.cidrs().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn cidrs_aligned() {
+        let range = "10.0.0.0-10.0.0.255".parse::<IPRange>().unwrap();
+        assert_eq!(
+            cidrs::<IPv4>(&["10.0.0.0/24"]),
+            range.cidrs().collect::<Vec<_>>()
+        );
+
+        let range = "10.0.0.1-10.0.0.1".parse::<IPRange>().unwrap();
+        assert_eq!(
+            cidrs::<IPv4>(&["10.0.0.1/32"]),
+            range.cidrs().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn cidrs_full_address_space() {
+        let range = "0.0.0.0-255.255.255.255".parse::<IPRange>().unwrap();
+        assert_eq!(
+            cidrs::<IPv4>(&["0.0.0.0/0"]),
+            range.cidrs().collect::<Vec<_>>()
+        );
+
+        let range = "::-ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"
+            .parse::<IPRange<IPv6>>()
+            .unwrap();
+        assert_eq!(cidrs::<IPv6>(&["::/0"]), range.cidrs().collect::<Vec<_>>());
+
+        let range = "255.255.255.254-255.255.255.255"
+            .parse::<IPRange>()
+            .unwrap();
+        assert_eq!(
+            cidrs::<IPv4>(&["255.255.255.254/31"]),
+            range.cidrs().collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn cidrs_ipv6() {
+        let range = "2001:db8::ffff - 2001:db8::1:1"
+            .parse::<IPRange<IPv6>>()
+            .unwrap();
+        let expected = cidrs::<IPv6>(&["2001:db8::ffff/128", "2001:db8::1:0/127"]);
+
+        assert_eq!(expected, range.cidrs().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn cidrs_roundtrip() {
+        let cidr = "172.16.0.0/12".parse::<CIDR>().unwrap();
+        let range = IPRange::from(cidr);
+
+        assert_eq!(vec![cidr], range.cidrs().collect::<Vec<_>>());
+    }
+}
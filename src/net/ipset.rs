@@ -0,0 +1,364 @@
+use crate::net::address_family::{all_ones, raw_from_u128};
+use crate::net::AddressFamily;
+use crate::net::IPRange;
+use crate::net::IPv4;
+use crate::net::CIDR;
+
+/// Set of addresses stored as sorted, disjoint and non adjacent intervals of
+/// raw addresses, so that large networks cost as much as a single host.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct IPSet<A: AddressFamily = IPv4> {
+    intervals: Vec<(A::Raw, A::Raw)>,
+}
+
+impl<A: AddressFamily> Default for IPSet<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<A: AddressFamily> From<CIDR<A>> for IPSet<A> {
+    fn from(cidr: CIDR<A>) -> Self {
+        Self::from(IPRange::from(cidr))
+    }
+}
+
+impl<A: AddressFamily> From<IPRange<A>> for IPSet<A> {
+    fn from(range: IPRange<A>) -> Self {
+        Self {
+            intervals: vec![(range.first().to_raw(), range.last().to_raw())],
+        }
+    }
+}
+
+impl<A: AddressFamily> FromIterator<CIDR<A>> for IPSet<A> {
+    fn from_iter<T: IntoIterator<Item = CIDR<A>>>(iter: T) -> Self {
+        Self::from_intervals(iter.into_iter().map(|cidr| {
+            let range = IPRange::from(cidr);
+            (range.first().to_u128(), range.last().to_u128())
+        }))
+    }
+}
+
+impl<A: AddressFamily> FromIterator<IPRange<A>> for IPSet<A> {
+    fn from_iter<T: IntoIterator<Item = IPRange<A>>>(iter: T) -> Self {
+        Self::from_intervals(
+            iter.into_iter()
+                .map(|range| (range.first().to_u128(), range.last().to_u128())),
+        )
+    }
+}
+
+impl<A: AddressFamily> IPSet<A> {
+    pub fn new() -> Self {
+        Self {
+            intervals: Vec::new(),
+        }
+    }
+
+    // Sorts and merges overlapping and adjacent intervals.
+    fn from_intervals(intervals: impl Iterator<Item = (u128, u128)>) -> Self {
+        let mut intervals: Vec<(u128, u128)> = intervals.collect();
+        intervals.sort_unstable();
+
+        let mut merged: Vec<(u128, u128)> = Vec::new();
+        for (first, last) in intervals {
+            match merged.last_mut() {
+                Some((_, end)) if end.checked_add(1).is_none_or(|next| first <= next) => {
+                    *end = last.max(*end);
+                }
+                _ => merged.push((first, last)),
+            }
+        }
+
+        Self {
+            intervals: merged
+                .into_iter()
+                .map(|(first, last)| (raw_from_u128::<A>(first), raw_from_u128::<A>(last)))
+                .collect(),
+        }
+    }
+
+    fn intervals(&self) -> impl Iterator<Item = (u128, u128)> + '_ {
+        self.intervals
+            .iter()
+            .map(|(first, last)| ((*first).into(), (*last).into()))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// Number of addresses in the set, saturating at `u128::MAX`.
+    pub fn addresses(&self) -> u128 {
+        self.intervals().fold(0u128, |total, (first, last)| {
+            total.saturating_add((last - first).saturating_add(1))
+        })
+    }
+
+    pub fn insert(&mut self, cidr: CIDR<A>) {
+        *self = self.union(&Self::from(cidr));
+    }
+
+    pub fn remove(&mut self, cidr: CIDR<A>) {
+        *self = self.difference(&Self::from(cidr));
+    }
+
+    pub fn contains_ip(&self, ip: &A) -> bool {
+        let ip = ip.to_u128();
+
+        self.intervals()
+            .any(|(first, last)| first <= ip && ip <= last)
+    }
+
+    pub fn contains_cidr(&self, cidr: &CIDR<A>) -> bool {
+        let range = IPRange::from(*cidr);
+        let (start, end) = (range.first().to_u128(), range.last().to_u128());
+
+        self.intervals()
+            .any(|(first, last)| first <= start && end <= last)
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::from_intervals(self.intervals().chain(other.intervals()))
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let mut left = self.intervals().peekable();
+        let mut right = other.intervals().peekable();
+
+        while let (Some(&(first, last)), Some(&(other_first, other_last))) =
+            (left.peek(), right.peek())
+        {
+            let start = first.max(other_first);
+            let end = last.min(other_last);
+            if start <= end {
+                intervals.push((start, end));
+            }
+
+            if last < other_last {
+                left.next();
+            } else {
+                right.next();
+            }
+        }
+
+        Self::from_intervals(intervals.into_iter())
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.intersection(&other.complement())
+    }
+
+    /// Every address of the family that isn't in the set.
+    pub fn complement(&self) -> Self {
+        let mut intervals = Vec::new();
+        let mut next = Some(0u128);
+        for (first, last) in self.intervals() {
+            if let Some(next) = next {
+                if next < first {
+                    intervals.push((next, first - 1));
+                }
+            }
+            next = last.checked_add(1).filter(|next| *next <= all_ones::<A>());
+        }
+        if let Some(next) = next {
+            intervals.push((next, all_ones::<A>()));
+        }
+
+        Self::from_intervals(intervals.into_iter())
+    }
+
+    pub fn ranges(&self) -> impl Iterator<Item = IPRange<A>> + '_ {
+        self.intervals()
+            .map(|(first, last)| IPRange::new(A::from_u128(first), A::from_u128(last)).unwrap())
+    }
+
+    /// Smallest set of CIDRs covering the set, in ascending order.
+    pub fn cidrs(&self) -> impl Iterator<Item = CIDR<A>> + '_ {
+        self.ranges().flat_map(|range| range.cidrs())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IPSet;
+    use crate::net::{cidrs, AddressFamily, IPRange, IPv4, IPv6};
+
+    fn set<A: AddressFamily>(raw: &[&str]) -> IPSet<A> {
+        cidrs(raw).into_iter().collect()
+    }
+
+    fn strings<A: AddressFamily>(set: &IPSet<A>) -> Vec<String> {
+        set.cidrs().map(|cidr| cidr.to_string()).collect()
+    }
+
+    #[test]
+    fn from_cidrs() {
+        let ips = set::<IPv4>(&["10.0.1.0/24", "10.0.0.0/24", "10.0.0.42/32", "10.0.3.0/24"]);
+
+        assert_eq!(vec!["10.0.0.0/23", "10.0.3.0/24"], strings(&ips));
+        assert_eq!(768, ips.addresses());
+    }
+
+    #[test]
+    fn from_range() {
+        let ips = IPSet::from("10.0.0.5-10.0.0.9".parse::<IPRange>().unwrap());
+
+        assert_eq!(
+            vec!["10.0.0.5/32", "10.0.0.6/31", "10.0.0.8/31"],
+            strings(&ips)
+        );
+    }
+
+    #[test]
+    fn empty() {
+        let ips = IPSet::<IPv4>::new();
+
+        assert!(ips.is_empty());
+        assert_eq!(0, ips.addresses());
+        assert_eq!(Vec::<String>::new(), strings(&ips));
+    }
+
+    #[test]
+    fn insert_remove() {
+        let mut ips = IPSet::<IPv4>::new();
+        ips.insert("10.0.0.0/24".parse().unwrap());
+        ips.insert("10.0.1.0/24".parse().unwrap());
+        assert_eq!(vec!["10.0.0.0/23"], strings(&ips));
+
+        ips.remove("10.0.0.128/25".parse().unwrap());
+        assert_eq!(vec!["10.0.0.0/25", "10.0.1.0/24"], strings(&ips));
+    }
+
+    #[test]
+    fn contains() {
+        let ips = set::<IPv4>(&["10.0.0.0/24", "10.0.1.0/25"]);
+
+        assert!(ips.contains_ip(&IPv4::new(10, 0, 0, 0)));
+        assert!(ips.contains_ip(&IPv4::new(10, 0, 1, 127)));
+        assert!(!ips.contains_ip(&IPv4::new(10, 0, 1, 128)));
+
+        assert!(ips.contains_cidr(&"10.0.0.128/25".parse().unwrap()));
+        assert!(ips.contains_cidr(&"10.0.0.128/24".parse().unwrap()));
+        assert!(ips.contains_cidr(&"10.0.0.0/24".parse().unwrap()));
+        assert!(!ips.contains_cidr(&"10.0.0.0/23".parse().unwrap()));
+        assert!(!ips.contains_cidr(&"10.0.2.0/32".parse().unwrap()));
+    }
+
+    #[test]
+    fn union() {
+        let ips = set::<IPv4>(&["10.0.0.0/24", "10.0.4.0/24"]);
+        let other = set::<IPv4>(&["10.0.1.0/24", "10.0.4.128/25", "192.168.0.0/16"]);
+
+        assert_eq!(
+            vec!["10.0.0.0/23", "10.0.4.0/24", "192.168.0.0/16"],
+            strings(&ips.union(&other))
+        );
+    }
+
+    #[test]
+    fn intersection() {
+        let ips = set::<IPv4>(&["10.0.0.0/16", "192.168.0.0/24"]);
+        let other = set::<IPv4>(&["10.0.42.0/24", "10.1.0.0/16", "192.168.0.128/25"]);
+
+        assert_eq!(
+            vec!["10.0.42.0/24", "192.168.0.128/25"],
+            strings(&ips.intersection(&other))
+        );
+        assert!(ips
+            .intersection(&set::<IPv4>(&["172.16.0.0/12"]))
+            .is_empty());
+    }
+
+    #[test]
+    fn difference() {
+        let ips = set::<IPv4>(&["10.0.0.0/22"]);
+        let other = set::<IPv4>(&["10.0.1.0/24", "10.0.3.255/32"]);
+
+        assert_eq!(
+            vec![
+                "10.0.0.0/24",
+                "10.0.2.0/24",
+                "10.0.3.0/25",
+                "10.0.3.128/26",
+                "10.0.3.192/27",
+                "10.0.3.224/28",
+                "10.0.3.240/29",
+                "10.0.3.248/30",
+                "10.0.3.252/31",
+                "10.0.3.254/32",
+            ],
+            strings(&ips.difference(&other))
+        );
+        assert!(other.difference(&ips).is_empty());
+    }
+
+    #[test]
+    fn complement() {
+        let ips = set::<IPv4>(&["0.0.0.0/1"]);
+        assert_eq!(vec!["128.0.0.0/1"], strings(&ips.complement()));
+
+        let ips = set::<IPv4>(&["10.0.0.0/8", "255.255.255.255/32"]);
+        assert_eq!(
+            vec![
+                "0.0.0.0/5",
+                "8.0.0.0/7",
+                "11.0.0.0/8",
+                "12.0.0.0/6",
+                "16.0.0.0/4",
+                "32.0.0.0/3",
+                "64.0.0.0/2",
+                "128.0.0.0/2",
+                "192.0.0.0/3",
+                "224.0.0.0/4",
+                "240.0.0.0/5",
+                "248.0.0.0/6",
+                "252.0.0.0/7",
+                "254.0.0.0/8",
+                "255.0.0.0/9",
+                "255.128.0.0/10",
+                "255.192.0.0/11",
+                "255.224.0.0/12",
+                "255.240.0.0/13",
+                "255.248.0.0/14",
+                "255.252.0.0/15",
+                "255.254.0.0/16",
+                "255.255.0.0/17",
+                "255.255.128.0/18",
+                "255.255.192.0/19",
+                "255.255.224.0/20",
+                "255.255.240.0/21",
+                "255.255.248.0/22",
+                "255.255.252.0/23",
+                "255.255.254.0/24",
+                "255.255.255.0/25",
+                "255.255.255.128/26",
+                "255.255.255.192/27",
+                "255.255.255.224/28",
+                "255.255.255.240/29",
+                "255.255.255.248/30",
+                "255.255.255.252/31",
+                "255.255.255.254/32",
+            ],
+            strings(&ips.complement())
+        );
+
+        assert_eq!(
+            vec!["0.0.0.0/0"],
+            strings(&IPSet::<IPv4>::new().complement())
+        );
+        assert!(set::<IPv4>(&["0.0.0.0/0"]).complement().is_empty());
+    }
+
+    #[test]
+    fn ipv6() {
+        let ips = set::<IPv6>(&["2001:db8::/32"]);
+        let other = set::<IPv6>(&["2001:db8:8000::/33"]);
+
+        assert_eq!(vec!["2001:db8::/33"], strings(&ips.difference(&other)));
+        assert_eq!(vec!["::/0"], strings(&ips.union(&ips.complement())));
+        assert_eq!(u128::MAX, ips.union(&ips.complement()).addresses());
+    }
+}
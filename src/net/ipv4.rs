@@ -1,5 +1,8 @@
+use crate::net::special_purpose::IPV4_SPECIAL_PURPOSE;
+use crate::net::AddressFamily;
 use crate::net::IPClass;
 use crate::net::IPKind;
+use crate::net::SpecialPurposeBlock;
 use core::fmt;
 use std::net;
 
@@ -8,7 +11,17 @@ pub enum IPParsingError {
     InvalidFormat,
 }
 
-#[derive(PartialEq, Eq)]
+impl fmt::Display for IPParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            IPParsingError::InvalidFormat => write!(f, "invalid address format"),
+        }
+    }
+}
+
+impl std::error::Error for IPParsingError {}
+
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
 pub struct IPv4(u32);
 
 impl fmt::Debug for IPv4 {
@@ -49,6 +62,31 @@ impl std::str::FromStr for IPv4 {
     }
 }
 
+impl AddressFamily for IPv4 {
+    type Raw = u32;
+
+    const NAME: &'static str = "IPv4";
+    const BITS: u8 = 32;
+    const HAS_BROADCAST: bool = true;
+    const DEFAULT_ADDRESS_SPACE: &'static str = "Internet";
+
+    fn from_raw(raw: u32) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> u32 {
+        self.0
+    }
+
+    fn class(&self) -> Option<IPClass> {
+        Some(IPv4::class(self))
+    }
+
+    fn special_purpose_registry() -> &'static [SpecialPurposeBlock] {
+        &IPV4_SPECIAL_PURPOSE
+    }
+}
+
 impl IPv4 {
     pub fn new(a: u8, b: u8, c: u8, d: u8) -> Self {
         let mut addr = (a as u32) << 24;
@@ -81,28 +119,30 @@ impl IPv4 {
     }
 
     pub fn kind(&self) -> IPKind {
-        let octets = self.octets();
-        let first_octet = (octets >> 24 & 0xFF) as u8;
-        let second_octet = (octets >> 16 & 0xFF) as u8;
-        let third_octet = (octets >> 8 & 0xFF) as u8;
-
-        match (first_octet, second_octet, third_octet) {
-            (10, _, _) => IPKind::Private,
-            (169, 254, _) => IPKind::Special("link-local"),
-            (172, 16..=31, _) => IPKind::Private,
-            (192, 168, _) => IPKind::Private,
-            (192, 0, 2) => IPKind::Special("documentation"),
-            (198, 51, 100) => IPKind::Special("documentation"),
-            (203, 0, 113) => IPKind::Special("documentation"),
-            (127, _, _) => IPKind::Special("localhost"),
-            _ => IPKind::Public,
-        }
+        AddressFamily::kind(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IPv4 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IPv4 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+
+        raw.parse().map_err(serde::de::Error::custom)
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{IPClass, IPKind, IPParsingError, IPv4};
+    use crate::net::{AddressFamily, CIDR};
 
     #[test]
     fn debug_display() {
@@ -255,4 +295,78 @@ mod tests {
         let address = "203.0.113.254".parse::<IPv4>().unwrap();
         assert_eq!(IPKind::Special("documentation"), address.kind());
     }
+
+    #[test]
+    fn kind_special_purpose_registry() {
+        let address = "100.64.0.1".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("Shared Address Space"), address.kind());
+        let address = "100.127.255.254".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("Shared Address Space"), address.kind());
+        let address = "100.128.0.1".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Public, address.kind());
+
+        let address = "198.19.255.1".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("Benchmarking"), address.kind());
+        let address = "224.0.0.251".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("multicast"), address.kind());
+        let address = "240.0.0.1".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("Reserved"), address.kind());
+        let address = "0.1.2.3".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("This network"), address.kind());
+        let address = "0.0.0.0".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("This host on this network"), address.kind());
+        let address = "192.0.0.42".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("IETF Protocol Assignments"), address.kind());
+        let address = "192.0.0.9".parse::<IPv4>().unwrap();
+        assert_eq!(
+            IPKind::Special("Port Control Protocol Anycast"),
+            address.kind()
+        );
+        let address = "255.255.255.255".parse::<IPv4>().unwrap();
+        assert_eq!(IPKind::Special("Limited Broadcast"), address.kind());
+    }
+
+    #[test]
+    fn special_purpose() {
+        let address = "172.20.1.1".parse::<IPv4>().unwrap();
+        let block = address.special_purpose().unwrap();
+
+        assert_eq!("Private-Use", block.name);
+        assert_eq!("RFC 1918", block.rfc);
+        assert_eq!(
+            "172.16.0.0/12".parse::<CIDR>().unwrap(),
+            block.network::<IPv4>()
+        );
+        assert!(block.source);
+        assert!(block.destination);
+        assert!(block.forwardable);
+        assert!(!block.globally_reachable);
+
+        let address = "8.8.8.8".parse::<IPv4>().unwrap();
+        assert_eq!(None, address.special_purpose());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            "\"10.12.23.43\"",
+            serde_json::to_string(&IPv4::new(10, 12, 23, 43)).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            IPv4::new(10, 12, 23, 43),
+            serde_json::from_str::<IPv4>("\"10.12.23.43\"").unwrap()
+        );
+        assert_eq!(
+            "invalid address format",
+            serde_json::from_str::<IPv4>("\"10.12.23\"")
+                .unwrap_err()
+                .to_string()
+        );
+    }
 }
@@ -0,0 +1,250 @@
+use crate::net::special_purpose::IPV6_SPECIAL_PURPOSE;
+use crate::net::AddressFamily;
+use crate::net::IPClass;
+use crate::net::IPKind;
+use crate::net::IPParsingError;
+use crate::net::SpecialPurposeBlock;
+use core::fmt;
+use std::net;
+
+#[derive(PartialEq, Eq, Clone, Copy, PartialOrd, Ord, Hash)]
+pub struct IPv6(u128);
+
+impl fmt::Debug for IPv6 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self, f)?;
+        write!(f, " (")?;
+        fmt::Binary::fmt(&self, f)?;
+        write!(f, ")")?;
+        Ok(())
+    }
+}
+
+impl fmt::Display for IPv6 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", net::Ipv6Addr::from(self.0))
+    }
+}
+
+impl fmt::Binary for IPv6 {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let segments = self.segments();
+        for (i, segment) in segments.iter().enumerate() {
+            if i > 0 {
+                write!(f, ":")?;
+            }
+            write!(f, "{:016b}", segment)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for IPv6 {
+    type Err = IPParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let net_ip = s
+            .parse::<net::Ipv6Addr>()
+            .map_err(|_| IPParsingError::InvalidFormat)?;
+
+        Ok(Self(u128::from(net_ip)))
+    }
+}
+
+impl AddressFamily for IPv6 {
+    type Raw = u128;
+
+    const NAME: &'static str = "IPv6";
+    const BITS: u8 = 128;
+    const HAS_BROADCAST: bool = false;
+    const DEFAULT_ADDRESS_SPACE: &'static str = "Global Unicast";
+
+    fn from_raw(raw: u128) -> Self {
+        Self(raw)
+    }
+
+    fn to_raw(&self) -> u128 {
+        self.0
+    }
+
+    fn class(&self) -> Option<IPClass> {
+        None
+    }
+
+    fn special_purpose_registry() -> &'static [SpecialPurposeBlock] {
+        &IPV6_SPECIAL_PURPOSE
+    }
+}
+
+impl IPv6 {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> Self {
+        Self(u128::from(net::Ipv6Addr::new(a, b, c, d, e, f, g, h)))
+    }
+
+    pub fn new_from_raw_bytes(addr: u128) -> Self {
+        Self(addr)
+    }
+
+    pub fn octets(&self) -> u128 {
+        self.0
+    }
+
+    pub fn segments(&self) -> [u16; 8] {
+        net::Ipv6Addr::from(self.0).segments()
+    }
+
+    pub fn kind(&self) -> IPKind {
+        AddressFamily::kind(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for IPv6 {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for IPv6 {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = String::deserialize(deserializer)?;
+
+        raw.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IPKind, IPParsingError, IPv6};
+    use crate::net::{AddressFamily, CIDR};
+
+    #[test]
+    fn debug_display() {
+        let ip = IPv6::new(0x2001, 0xdb8, 0, 0, 0, 0, 0, 1);
+        assert_eq!(
+            "2001:db8::1 (0010000000000001:0000110110111000:0000000000000000:0000000000000000:0000000000000000:0000000000000000:0000000000000000:0000000000000001)",
+            format!("{:?}", ip)
+        )
+    }
+
+    #[test]
+    fn string_display() {
+        let ip = IPv6::new(0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329);
+        assert_eq!("2001:db8::ff00:42:8329", format!("{}", ip))
+    }
+
+    #[test]
+    fn binary_display() {
+        let ip = IPv6::new(0xffff, 0, 0, 0, 0, 0, 0, 0x8000);
+        assert_eq!(
+            "1111111111111111:0000000000000000:0000000000000000:0000000000000000:0000000000000000:0000000000000000:0000000000000000:1000000000000000",
+            format!("{:b}", ip)
+        )
+    }
+
+    #[test]
+    fn parse_success() {
+        let ip = "2001:db8::ff00:42:8329".parse::<IPv6>().unwrap();
+        assert_eq!(IPv6::new(0x2001, 0xdb8, 0, 0, 0, 0xff00, 0x42, 0x8329), ip)
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err(IPParsingError::InvalidFormat),
+            "2001:db8::ff00::8329".parse::<IPv6>()
+        );
+
+        assert_eq!(
+            Err(IPParsingError::InvalidFormat),
+            "192.168.13.37".parse::<IPv6>()
+        )
+    }
+
+    #[test]
+    fn octets() {
+        let address = "::1".parse::<IPv6>().unwrap();
+        assert_eq!(1, address.octets())
+    }
+
+    #[test]
+    fn kind_private() {
+        let address = "fc00::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Private, address.kind());
+        let address = "fdff:ffff::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Private, address.kind());
+    }
+
+    #[test]
+    fn kind_public() {
+        let address = "2a00:1450:4007:80e::200e".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Public, address.kind());
+        let address = "2001:db9::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Public, address.kind());
+        let address = "fe00::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Public, address.kind());
+    }
+
+    #[test]
+    fn kind_special() {
+        let address = "::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("localhost"), address.kind());
+        let address = "fe80::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("link-local"), address.kind());
+        let address = "febf:ffff::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("link-local"), address.kind());
+        let address = "2001:db8::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("documentation"), address.kind());
+        let address = "ff02::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("multicast"), address.kind());
+        let address = "64:ff9b::192.0.2.1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("IPv4-IPv6 Translation"), address.kind());
+        let address = "2001:0:4136:e378::1".parse::<IPv6>().unwrap();
+        assert_eq!(IPKind::Special("TEREDO"), address.kind());
+        let address = "2001:1::1".parse::<IPv6>().unwrap();
+        assert_eq!(
+            IPKind::Special("Port Control Protocol Anycast"),
+            address.kind()
+        );
+    }
+
+    #[test]
+    fn special_purpose() {
+        let address = "fe80::1".parse::<IPv6>().unwrap();
+        let block = address.special_purpose().unwrap();
+
+        assert_eq!("RFC 4291", block.rfc);
+        assert_eq!(
+            "fe80::/10".parse::<CIDR<IPv6>>().unwrap(),
+            block.network::<IPv6>()
+        );
+        assert!(block.source);
+        assert!(block.destination);
+        assert!(!block.forwardable);
+        assert!(!block.globally_reachable);
+
+        let address = "2a00:1450:4007:80e::200e".parse::<IPv6>().unwrap();
+        assert_eq!(None, address.special_purpose());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        let address = "2001:db8::1".parse::<IPv6>().unwrap();
+
+        assert_eq!("\"2001:db8::1\"", serde_json::to_string(&address).unwrap());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        assert_eq!(
+            "2001:db8::1".parse::<IPv6>().unwrap(),
+            serde_json::from_str::<IPv6>("\"2001:db8::1\"").unwrap()
+        );
+        assert!(serde_json::from_str::<IPv6>("\"10.12.23.43\"").is_err());
+    }
+}
@@ -1,3 +1,5 @@
+use crate::net::address_family::{all_ones, raw_from_u128};
+use crate::net::AddressFamily;
 use crate::net::IPv4;
 use core::fmt;
 
@@ -5,122 +7,261 @@ use core::fmt;
 pub enum MaskParsingError {
     InvalidRange,
     InvalidFormat,
+    /// The netmask or wildcard mask isn't made of contiguous bits. Holds the
+    /// position of the first offending bit, counted from 1 on the left.
+    NonContiguous(u8),
 }
 
+impl fmt::Display for MaskParsingError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MaskParsingError::InvalidRange => write!(f, "mask length out of range"),
+            MaskParsingError::InvalidFormat => write!(f, "invalid mask format"),
+            MaskParsingError::NonContiguous(bit) => write!(
+                f,
+                "mask must be contiguous but bit {} breaks contiguity",
+                bit
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MaskParsingError {}
+
 #[derive(PartialEq, Eq, Copy, Clone, PartialOrd)]
-pub struct Mask(u32);
+pub struct Mask<A: AddressFamily = IPv4>(A::Raw);
 
-impl fmt::Debug for Mask {
+impl<A: AddressFamily> fmt::Debug for Mask<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "/{}", self.prefix_length())
     }
 }
 
-impl fmt::Display for Mask {
+impl<A: AddressFamily> fmt::Display for Mask<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let [a, b, c, d] = crate::net::group_octets(self.0);
-        write!(f, "{}.{}.{}.{}", a, b, c, d)
+        write!(f, "{}", A::from_raw(self.0))
     }
 }
 
-impl fmt::Binary for Mask {
+impl<A: AddressFamily> fmt::Binary for Mask<A> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        let [a, b, c, d] = crate::net::group_octets(self.0);
-        write!(f, "{:08b}.{:08b}.{:08b}.{:08b}", a, b, c, d)
+        write!(f, "{:b}", A::from_raw(self.0))
     }
 }
 
-impl std::str::FromStr for Mask {
+impl<A: AddressFamily> std::str::FromStr for Mask<A> {
     type Err = MaskParsingError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mask: u8 = s.parse().map_err(|_| MaskParsingError::InvalidFormat)?;
+        if let Ok(mask) = s.parse::<u8>() {
+            return Self::new(mask);
+        }
+
+        let address = s
+            .parse::<A>()
+            .map_err(|_| MaskParsingError::InvalidFormat)?;
 
-        Self::new(mask)
+        Self::from_address(&address)
     }
 }
 
-impl Mask {
+impl<A: AddressFamily> Mask<A> {
     pub fn new(value: u8) -> Result<Self, MaskParsingError> {
-        if value > 32 {
+        if value > A::BITS {
             return Err(MaskParsingError::InvalidRange);
         }
 
-        let mut mask: u32 = 0;
+        let mut mask: u128 = 0;
 
         if value > 0 {
-            mask = 0xFFFFFFFF << (32 - value);
+            mask = all_ones::<A>() << (A::BITS - value);
         }
 
-        Ok(Self(mask))
+        Ok(Self::from_bits(mask))
     }
 
-    pub fn prefix_length(&self) -> u8 {
-        let mut zeroes = 0;
-        for i in 0..32 {
-            if self.0 >> i & 1 == 0 {
-                zeroes += 1;
-                continue;
-            }
+    /// Builds a mask from its address notation, either as a netmask
+    /// (`255.255.255.0`) or as a wildcard mask (`0.0.0.255`).
+    pub fn from_address(address: &A) -> Result<Self, MaskParsingError> {
+        let netmask = address.to_u128();
+        let wildcard = !netmask & all_ones::<A>();
 
-            break;
+        if Self::is_contiguous(netmask) {
+            return Ok(Self::from_bits(netmask));
         }
 
-        32 - zeroes
+        if Self::is_contiguous(wildcard) {
+            return Ok(Self::from_bits(wildcard));
+        }
+
+        let leftmost_bit = netmask >> (A::BITS - 1);
+        let bits = if leftmost_bit == 1 { netmask } else { wildcard };
+        Err(MaskParsingError::NonContiguous(
+            Self::first_non_contiguous_bit(bits),
+        ))
     }
 
-    pub fn wildcard(&self) -> Mask {
-        Mask(!self.0)
+    fn is_contiguous(netmask: u128) -> bool {
+        let wildcard = !netmask & all_ones::<A>();
+
+        wildcard & wildcard.wrapping_add(1) == 0
     }
 
-    pub fn network_address(&self, ip: &IPv4) -> IPv4 {
-        let network_address = ip.octets() & self.0;
-        IPv4::new_from_raw_bytes(network_address)
+    fn first_non_contiguous_bit(netmask: u128) -> u8 {
+        let aligned = netmask << (128 - A::BITS as u32);
+        let ones = aligned.leading_ones();
+        let zeroes = (aligned << ones).leading_zeros();
+
+        (ones + zeroes + 1) as u8
     }
 
-    pub fn first_address(&self, ip: &IPv4) -> Option<IPv4> {
-        if self.prefix_length() >= 31 {
-            return None;
+    fn from_bits(bits: u128) -> Self {
+        Self(raw_from_u128::<A>(bits))
+    }
+
+    pub fn bits(&self) -> u128 {
+        self.0.into()
+    }
+
+    pub fn prefix_length(&self) -> u8 {
+        let zeroes = self.bits().trailing_zeros().min(A::BITS as u32) as u8;
+
+        A::BITS - zeroes
+    }
+
+    pub fn wildcard(&self) -> Mask<A> {
+        Self::from_bits(!self.bits())
+    }
+
+    pub fn network_address(&self, ip: &A) -> A {
+        let network_address = ip.to_u128() & self.bits();
+        A::from_u128(network_address)
+    }
+
+    // /31 (RFC 3021 point-to-point links) and /32 (host routes) networks
+    // don't reserve a network and broadcast address, like IPv6 networks
+    fn reserves_broadcast(&self) -> bool {
+        A::HAS_BROADCAST && self.prefix_length() < A::BITS - 1
+    }
+
+    pub fn first_address(&self, ip: &A) -> Option<A> {
+        let network = ip.to_u128() & self.bits();
+        if !self.reserves_broadcast() {
+            return Some(A::from_u128(network));
         }
 
-        let address = (ip.octets() & self.0) + 1;
-        Some(IPv4::new_from_raw_bytes(address))
+        Some(A::from_u128(network + 1))
     }
 
-    pub fn last_address(&self, ip: &IPv4) -> Option<IPv4> {
-        if self.prefix_length() >= 31 {
+    pub fn last_address(&self, ip: &A) -> Option<A> {
+        let last = (ip.to_u128() & self.bits()) | self.wildcard().bits();
+        if !self.reserves_broadcast() {
+            return Some(A::from_u128(last));
+        }
+
+        Some(A::from_u128(last - 1))
+    }
+
+    pub fn broadcast_address(&self, ip: &A) -> Option<A> {
+        if !self.reserves_broadcast() {
             return None;
         }
 
-        let address = (ip.octets() & self.0) + (self.wildcard().0 - 1);
-        Some(IPv4::new_from_raw_bytes(address))
+        let address = (ip.to_u128() & self.bits()) | self.wildcard().bits();
+        Some(A::from_u128(address))
+    }
+
+    /// Number of usable hosts in a network using this mask. Networks that
+    /// don't reserve a broadcast address (IPv6, IPv4 /31 and /32) count every
+    /// address, saturating at `u128::MAX` for `/0`.
+    pub fn hosts(&self) -> u128 {
+        if !self.reserves_broadcast() {
+            return self.wildcard().bits().saturating_add(1);
+        }
+
+        self.wildcard().bits() - 1
     }
 
-    pub fn broadcast_address(&self, ip: &IPv4) -> Option<IPv4> {
-        if self.prefix_length() >= 31 {
+    /// Longest mask whose networks provide at least `hosts` usable hosts, or
+    /// `None` if even `/0` is too small.
+    pub fn for_hosts(hosts: u128) -> Option<Self> {
+        (0..=A::BITS)
+            .rev()
+            .map(|prefix_length| Self::new(prefix_length).unwrap())
+            .find(|mask| mask.hosts() >= hosts)
+    }
+
+    /// Longest mask cutting networks using the `parent` mask in at least
+    /// `subnets` subnets, or `None` if there aren't enough bits left.
+    pub fn for_subnets(parent: &Self, subnets: u128) -> Option<Self> {
+        let bits = match subnets {
+            0 | 1 => 0,
+            _ => (subnets - 1).ilog2() + 1,
+        };
+        let prefix_length = parent.prefix_length() as u32 + bits;
+        if prefix_length > A::BITS as u32 {
             return None;
         }
 
-        let address = (ip.octets() & self.0) + self.wildcard().0;
-        Some(IPv4::new_from_raw_bytes(address))
+        Some(Self::new(prefix_length as u8).unwrap())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<A: AddressFamily> serde::Serialize for Mask<A> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(self.prefix_length())
     }
+}
+
+/// Accepts a prefix length, or any notation supported by `FromStr` from
+/// human readable formats. Other formats only hold the prefix length.
+#[cfg(feature = "serde")]
+impl<'de, A: AddressFamily> serde::Deserialize<'de> for Mask<A> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MaskVisitor<A: AddressFamily>(std::marker::PhantomData<A>);
+
+        impl<A: AddressFamily> serde::de::Visitor<'_> for MaskVisitor<A> {
+            type Value = Mask<A>;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "a prefix length, netmask or wildcard mask")
+            }
+
+            fn visit_u64<E: serde::de::Error>(self, value: u64) -> Result<Mask<A>, E> {
+                let value = u8::try_from(value).map_err(|_| MaskParsingError::InvalidRange);
+
+                value.and_then(Mask::new).map_err(E::custom)
+            }
+
+            fn visit_i64<E: serde::de::Error>(self, value: i64) -> Result<Mask<A>, E> {
+                let value =
+                    u64::try_from(value).map_err(|_| E::custom(MaskParsingError::InvalidFormat))?;
+
+                self.visit_u64(value)
+            }
 
-    pub fn hosts(&self) -> u32 {
-        if self.prefix_length() == 32 {
-            return 1;
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Mask<A>, E> {
+                value.parse().map_err(E::custom)
+            }
+        }
+
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_any(MaskVisitor(std::marker::PhantomData))
+        } else {
+            deserializer.deserialize_u8(MaskVisitor(std::marker::PhantomData))
         }
-        self.wildcard().0 - 1
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::{Mask, MaskParsingError};
-    use crate::net::IPv4;
+    use crate::net::{IPv4, IPv6};
 
     #[test]
     fn debug_display() {
-        assert_eq!("/24", format!("{:?}", Mask::new(24).unwrap()))
+        assert_eq!("/24", format!("{:?}", Mask::<IPv4>::new(24).unwrap()))
     }
 
     #[test]
@@ -138,6 +279,55 @@ mod tests {
         assert_eq!(Err(MaskParsingError::InvalidRange), "198".parse::<Mask>())
     }
 
+    #[test]
+    fn parse_netmask_success() {
+        assert_eq!(Mask(0xFFFFFF00), "255.255.255.0".parse::<Mask>().unwrap());
+        assert_eq!(Mask(0xFFFFFFFF), "255.255.255.255".parse::<Mask>().unwrap());
+        assert_eq!(Mask(0x00000000), "0.0.0.0".parse::<Mask>().unwrap());
+    }
+
+    #[test]
+    fn parse_wildcard_success() {
+        assert_eq!(Mask(0xFFFFFF00), "0.0.0.255".parse::<Mask>().unwrap());
+        assert_eq!(Mask(0xFFF00000), "0.15.255.255".parse::<Mask>().unwrap());
+    }
+
+    #[test]
+    fn parse_mask_non_contiguous() {
+        assert_eq!(
+            Err(MaskParsingError::NonContiguous(17)),
+            "255.0.255.0".parse::<Mask>()
+        );
+
+        assert_eq!(
+            Err(MaskParsingError::NonContiguous(25)),
+            "0.0.255.0".parse::<Mask>()
+        );
+
+        assert_eq!(
+            Err(MaskParsingError::NonContiguous(32)),
+            "255.255.255.253".parse::<Mask>()
+        );
+    }
+
+    #[test]
+    fn parse_ipv6_netmask() {
+        assert_eq!(
+            Mask::<IPv6>::new(64).unwrap(),
+            "ffff:ffff:ffff:ffff::".parse::<Mask<IPv6>>().unwrap()
+        );
+
+        assert_eq!(
+            Mask::<IPv6>::new(120).unwrap(),
+            "::ff".parse::<Mask<IPv6>>().unwrap()
+        );
+
+        assert_eq!(
+            Err(MaskParsingError::NonContiguous(25)),
+            "ffff:00ff::".parse::<Mask<IPv6>>()
+        );
+    }
+
     #[test]
     fn parse_mask_success() {
         assert_eq!(Mask(0xFFFFFF00), "24".parse::<Mask>().unwrap())
@@ -145,70 +335,76 @@ mod tests {
 
     #[test]
     fn string_display() {
-        assert_eq!("255.255.255.0", format!("{}", Mask::new(24).unwrap()));
-        assert_eq!("255.255.0.0", format!("{}", Mask::new(16).unwrap()));
-        assert_eq!("255.0.0.0", format!("{}", Mask::new(8).unwrap()));
-        assert_eq!("255.255.240.0", format!("{}", Mask::new(20).unwrap()));
+        assert_eq!(
+            "255.255.255.0",
+            format!("{}", Mask::<IPv4>::new(24).unwrap())
+        );
+        assert_eq!("255.255.0.0", format!("{}", Mask::<IPv4>::new(16).unwrap()));
+        assert_eq!("255.0.0.0", format!("{}", Mask::<IPv4>::new(8).unwrap()));
+        assert_eq!(
+            "255.255.240.0",
+            format!("{}", Mask::<IPv4>::new(20).unwrap())
+        );
     }
 
     #[test]
     fn binary_display() {
         assert_eq!(
             "11111111.11111111.11111111.00000000",
-            format!("{:b}", Mask::new(24).unwrap())
+            format!("{:b}", Mask::<IPv4>::new(24).unwrap())
         );
 
         assert_eq!(
             "11111111.11111111.00000000.00000000",
-            format!("{:b}", Mask::new(16).unwrap())
+            format!("{:b}", Mask::<IPv4>::new(16).unwrap())
         );
 
         assert_eq!(
             "11111111.11111111.11110000.00000000",
-            format!("{:b}", Mask::new(20).unwrap())
+            format!("{:b}", Mask::<IPv4>::new(20).unwrap())
         );
     }
 
     #[test]
     fn prefix_length() {
-        assert_eq!(24, Mask::new(24).unwrap().prefix_length())
+        assert_eq!(24, Mask::<IPv4>::new(24).unwrap().prefix_length())
     }
 
     #[test]
     fn get_wildcard() {
-        assert_eq!(Mask(0x00000000), Mask::new(32).unwrap().wildcard());
-        assert_eq!(Mask(0x000000FF), Mask::new(24).unwrap().wildcard());
-        assert_eq!(Mask(0x0000FFFF), Mask::new(16).unwrap().wildcard());
-        assert_eq!(Mask(0x00FFFFFF), Mask::new(8).unwrap().wildcard());
-        assert_eq!(Mask(0xFFFFFFFF), Mask::new(0).unwrap().wildcard());
+        assert_eq!(Mask(0x00000000), Mask::<IPv4>::new(32).unwrap().wildcard());
+        assert_eq!(Mask(0x000000FF), Mask::<IPv4>::new(24).unwrap().wildcard());
+        assert_eq!(Mask(0x0000FFFF), Mask::<IPv4>::new(16).unwrap().wildcard());
+        assert_eq!(Mask(0x00FFFFFF), Mask::<IPv4>::new(8).unwrap().wildcard());
+        assert_eq!(Mask(0xFFFFFFFF), Mask::<IPv4>::new(0).unwrap().wildcard());
     }
 
     #[test]
     fn network_address() {
         let host_address = IPv4::new(10, 42, 180, 53);
 
-        let mask = Mask::new(32).unwrap();
+        let mask = Mask::<IPv4>::new(32).unwrap();
         assert_eq!(
             IPv4::new(10, 42, 180, 53),
             mask.network_address(&host_address)
         );
 
-        let mask = Mask::new(24).unwrap();
+        let mask = Mask::<IPv4>::new(24).unwrap();
         assert_eq!(
             IPv4::new(10, 42, 180, 0),
             mask.network_address(&host_address)
         );
 
-        let mask = Mask::new(20).unwrap();
+        let mask = Mask::<IPv4>::new(20).unwrap();
         assert_eq!(
             IPv4::new(10, 42, 176, 0),
             mask.network_address(&host_address)
         );
 
-        let mask = Mask::new(16).unwrap();
+        let mask = Mask::<IPv4>::new(16).unwrap();
         assert_eq!(IPv4::new(10, 42, 0, 0), mask.network_address(&host_address));
 
-        let mask = Mask::new(0).unwrap();
+        let mask = Mask::<IPv4>::new(0).unwrap();
         assert_eq!(IPv4::new(0, 0, 0, 0), mask.network_address(&host_address));
     }
 
@@ -216,16 +412,22 @@ mod tests {
     fn first_address() {
         let host_address = IPv4::new(10, 42, 180, 53);
 
-        let mask = Mask::new(32).unwrap();
-        assert_eq!(None, mask.first_address(&host_address));
+        let mask = Mask::<IPv4>::new(32).unwrap();
+        assert_eq!(Some(host_address), mask.first_address(&host_address));
 
-        let mask = Mask::new(24).unwrap();
+        let mask = Mask::<IPv4>::new(31).unwrap();
+        assert_eq!(
+            Some(IPv4::new(10, 42, 180, 52)),
+            mask.first_address(&host_address)
+        );
+
+        let mask = Mask::<IPv4>::new(24).unwrap();
         assert_eq!(
             Some(IPv4::new(10, 42, 180, 1)),
             mask.first_address(&host_address)
         );
 
-        let mask = Mask::new(20).unwrap();
+        let mask = Mask::<IPv4>::new(20).unwrap();
         assert_eq!(
             Some(IPv4::new(10, 42, 176, 1)),
             mask.first_address(&host_address)
@@ -236,16 +438,22 @@ mod tests {
     fn last_address() {
         let host_address = IPv4::new(10, 42, 180, 53);
 
-        let mask = Mask::new(32).unwrap();
-        assert_eq!(None, mask.last_address(&host_address));
+        let mask = Mask::<IPv4>::new(32).unwrap();
+        assert_eq!(Some(host_address), mask.last_address(&host_address));
 
-        let mask = Mask::new(24).unwrap();
+        let mask = Mask::<IPv4>::new(31).unwrap();
+        assert_eq!(
+            Some(IPv4::new(10, 42, 180, 53)),
+            mask.last_address(&host_address)
+        );
+
+        let mask = Mask::<IPv4>::new(24).unwrap();
         assert_eq!(
             Some(IPv4::new(10, 42, 180, 254)),
             mask.last_address(&host_address)
         );
 
-        let mask = Mask::new(20).unwrap();
+        let mask = Mask::<IPv4>::new(20).unwrap();
         assert_eq!(
             Some(IPv4::new(10, 42, 191, 254)),
             mask.last_address(&host_address)
@@ -256,16 +464,19 @@ mod tests {
     fn broadcast_address() {
         let host_address = IPv4::new(10, 42, 180, 53);
 
-        let mask = Mask::new(32).unwrap();
+        let mask = Mask::<IPv4>::new(32).unwrap();
+        assert_eq!(None, mask.broadcast_address(&host_address));
+
+        let mask = Mask::<IPv4>::new(31).unwrap();
         assert_eq!(None, mask.broadcast_address(&host_address));
 
-        let mask = Mask::new(24).unwrap();
+        let mask = Mask::<IPv4>::new(24).unwrap();
         assert_eq!(
             Some(IPv4::new(10, 42, 180, 255)),
             mask.broadcast_address(&host_address)
         );
 
-        let mask = Mask::new(20).unwrap();
+        let mask = Mask::<IPv4>::new(20).unwrap();
         assert_eq!(
             Some(IPv4::new(10, 42, 191, 255)),
             mask.broadcast_address(&host_address)
@@ -274,19 +485,194 @@ mod tests {
 
     #[test]
     fn hosts() {
-        let mask = Mask::new(32).unwrap();
+        let mask = Mask::<IPv4>::new(32).unwrap();
         assert_eq!(1, mask.hosts());
 
-        let mask = Mask::new(24).unwrap();
+        let mask = Mask::<IPv4>::new(31).unwrap();
+        assert_eq!(2, mask.hosts());
+
+        let mask = Mask::<IPv4>::new(30).unwrap();
+        assert_eq!(2, mask.hosts());
+
+        let mask = Mask::<IPv4>::new(24).unwrap();
         assert_eq!(254, mask.hosts());
 
-        let mask = Mask::new(20).unwrap();
+        let mask = Mask::<IPv4>::new(20).unwrap();
         assert_eq!(4094, mask.hosts());
 
-        let mask = Mask::new(16).unwrap();
+        let mask = Mask::<IPv4>::new(16).unwrap();
         assert_eq!(65534, mask.hosts());
 
-        let mask = Mask::new(8).unwrap();
+        let mask = Mask::<IPv4>::new(8).unwrap();
         assert_eq!(16777214, mask.hosts());
     }
+
+    #[test]
+    fn for_hosts() {
+        let prefix_length = |hosts| Mask::<IPv4>::for_hosts(hosts).map(|m| m.prefix_length());
+
+        assert_eq!(Some(32), prefix_length(0));
+        assert_eq!(Some(32), prefix_length(1));
+        assert_eq!(Some(31), prefix_length(2));
+        assert_eq!(Some(29), prefix_length(3));
+        assert_eq!(Some(24), prefix_length(254));
+        assert_eq!(Some(23), prefix_length(255));
+        assert_eq!(Some(21), prefix_length(1200));
+        assert_eq!(Some(0), prefix_length(u32::MAX as u128 - 1));
+        assert_eq!(None, prefix_length(u32::MAX as u128));
+    }
+
+    #[test]
+    fn for_hosts_ipv6() {
+        let prefix_length = |hosts| Mask::<IPv6>::for_hosts(hosts).map(|m| m.prefix_length());
+
+        assert_eq!(Some(128), prefix_length(1));
+        assert_eq!(Some(127), prefix_length(2));
+        assert_eq!(Some(126), prefix_length(3));
+        assert_eq!(Some(64), prefix_length(1 << 64));
+        assert_eq!(Some(0), prefix_length(u128::MAX));
+    }
+
+    #[test]
+    fn for_subnets() {
+        let parent = Mask::<IPv4>::new(20).unwrap();
+        let prefix_length =
+            |subnets| Mask::for_subnets(&parent, subnets).map(|m| m.prefix_length());
+
+        assert_eq!(Some(20), prefix_length(0));
+        assert_eq!(Some(20), prefix_length(1));
+        assert_eq!(Some(21), prefix_length(2));
+        assert_eq!(Some(24), prefix_length(12));
+        assert_eq!(Some(24), prefix_length(16));
+        assert_eq!(Some(25), prefix_length(17));
+        assert_eq!(Some(32), prefix_length(1 << 12));
+        assert_eq!(None, prefix_length((1 << 12) + 1));
+        assert_eq!(None, prefix_length(u128::MAX));
+    }
+
+    #[test]
+    fn for_subnets_ipv6() {
+        let parent = Mask::<IPv6>::new(0).unwrap();
+
+        assert_eq!(
+            Some(128),
+            Mask::for_subnets(&parent, u128::MAX).map(|m| m.prefix_length())
+        );
+    }
+
+    #[test]
+    fn ipv6_string_display() {
+        assert_eq!(
+            "ffff:ffff:ffff:ffff::",
+            format!("{}", Mask::<IPv6>::new(64).unwrap())
+        );
+        assert_eq!(
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff",
+            format!("{}", Mask::<IPv6>::new(128).unwrap())
+        );
+        assert_eq!("::", format!("{}", Mask::<IPv6>::new(0).unwrap()));
+    }
+
+    #[test]
+    fn ipv6_parse_mask_not_in_range() {
+        assert_eq!(
+            Err(MaskParsingError::InvalidRange),
+            "129".parse::<Mask<IPv6>>()
+        );
+        assert_eq!(
+            Mask::<IPv6>::new(96).unwrap(),
+            "96".parse::<Mask<IPv6>>().unwrap()
+        );
+    }
+
+    #[test]
+    fn ipv6_prefix_length() {
+        assert_eq!(0, Mask::<IPv6>::new(0).unwrap().prefix_length());
+        assert_eq!(48, Mask::<IPv6>::new(48).unwrap().prefix_length());
+        assert_eq!(128, Mask::<IPv6>::new(128).unwrap().prefix_length());
+    }
+
+    #[test]
+    fn ipv6_addresses() {
+        let host_address = "2001:db8:abcd:12::42".parse::<IPv6>().unwrap();
+        let mask = Mask::<IPv6>::new(48).unwrap();
+
+        assert_eq!(
+            "2001:db8:abcd::".parse::<IPv6>().unwrap(),
+            mask.network_address(&host_address)
+        );
+        assert_eq!(
+            Some("2001:db8:abcd::".parse::<IPv6>().unwrap()),
+            mask.first_address(&host_address)
+        );
+        assert_eq!(
+            Some(
+                "2001:db8:abcd:ffff:ffff:ffff:ffff:ffff"
+                    .parse::<IPv6>()
+                    .unwrap()
+            ),
+            mask.last_address(&host_address)
+        );
+        assert_eq!(None, mask.broadcast_address(&host_address));
+    }
+
+    #[test]
+    fn ipv6_hosts() {
+        assert_eq!(1, Mask::<IPv6>::new(128).unwrap().hosts());
+        assert_eq!(2, Mask::<IPv6>::new(127).unwrap().hosts());
+        assert_eq!(1 << 64, Mask::<IPv6>::new(64).unwrap().hosts());
+        assert_eq!(u128::MAX, Mask::<IPv6>::new(0).unwrap().hosts());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serialize() {
+        assert_eq!(
+            "20",
+            serde_json::to_string(&Mask::<IPv4>::new(20).unwrap()).unwrap()
+        );
+        assert_eq!(
+            "128",
+            serde_json::to_string(&Mask::<IPv6>::new(128).unwrap()).unwrap()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize() {
+        let masks: Vec<Mask> =
+            serde_json::from_str(r#"[20, "20", "255.255.240.0", "0.0.15.255"]"#).unwrap();
+        assert_eq!(vec![Mask::new(20).unwrap(); 4], masks);
+
+        let mask: Mask<IPv6> = serde_json::from_str("64").unwrap();
+        assert_eq!(Mask::new(64).unwrap(), mask);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn deserialize_errors() {
+        let error = |raw| serde_json::from_str::<Mask>(raw).unwrap_err().to_string();
+
+        assert!(error("33").starts_with("mask length out of range"));
+        assert!(error("256").starts_with("mask length out of range"));
+        assert!(error("-1").starts_with("invalid mask format"));
+        assert!(error("\"nope\"").starts_with("invalid mask format"));
+        assert!(error("\"255.0.255.0\"")
+            .starts_with("mask must be contiguous but bit 17 breaks contiguity"));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn binary_roundtrip() {
+        let mask = Mask::<IPv4>::new(20).unwrap();
+        let raw = bincode::serialize(&mask).unwrap();
+        assert_eq!(vec![20], raw);
+        assert_eq!(mask, bincode::deserialize::<Mask>(&raw).unwrap());
+
+        let mask = Mask::<IPv6>::new(128).unwrap();
+        let raw = bincode::serialize(&mask).unwrap();
+        assert_eq!(mask, bincode::deserialize::<Mask<IPv6>>(&raw).unwrap());
+
+        assert!(bincode::deserialize::<Mask>(&[33]).is_err());
+    }
 }
@@ -1,14 +1,38 @@
+mod address_family;
 mod cidr;
+mod free_space;
 mod ipclass;
 mod ipkind;
+mod iprange;
+mod ipset;
 mod ipv4;
+mod ipv6;
 mod mask;
+mod overlaps;
+mod planner;
+mod prefix_trie;
+mod special_purpose;
+mod summarize;
+mod wildcard_mask;
 
-pub use self::cidr::{CIDRComparison, CIDRParsingError, CIDR};
+pub use self::address_family::AddressFamily;
+pub use self::cidr::{Addresses, CIDRComparison, CIDRParsingError, Split, CIDR};
+pub use self::free_space::FreeSpace;
 pub use self::ipclass::IPClass;
 pub use self::ipkind::IPKind;
+pub use self::iprange::{IPRange, IPRangeParsingError};
+pub use self::ipset::IPSet;
 pub use self::ipv4::{IPParsingError, IPv4};
+pub use self::ipv6::IPv6;
 pub use self::mask::{Mask, MaskParsingError};
+pub use self::overlaps::overlapping_pairs;
+pub use self::planner::{plan, Allocation, Plan, PlanningError, SubnetRequirement};
+pub use self::prefix_trie::{PrefixTrie, Subtree};
+pub use self::special_purpose::{
+    SpecialPurposeBlock, SpecialPurposeOverlap, IPV4_SPECIAL_PURPOSE, IPV6_SPECIAL_PURPOSE,
+};
+pub use self::summarize::{summarize, summarize_with_overcoverage};
+pub use self::wildcard_mask::{WildcardMask, WildcardMaskParsingError};
 
 pub fn group_octets(value: u32) -> [u8; 4] {
     let a = (value >> 24 & 0xFF) as u8;
@@ -18,3 +42,9 @@ pub fn group_octets(value: u32) -> [u8; 4] {
 
     [a, b, c, d]
 }
+
+/// Parses the CIDR fixtures shared by the tests of the net modules.
+#[cfg(test)]
+pub(crate) fn cidrs<A: AddressFamily>(raw: &[&str]) -> Vec<CIDR<A>> {
+    raw.iter().map(|cidr| cidr.parse().unwrap()).collect()
+}
@@ -0,0 +1,82 @@
+use crate::net::AddressFamily;
+use crate::net::CIDR;
+
+/// Indices of every pair of overlapping networks in `cidrs`, host bits being
+/// ignored. Pairs are ordered as `(i, j)` with `i < j`, in ascending order.
+pub fn overlapping_pairs<A: AddressFamily>(cidrs: &[CIDR<A>]) -> Vec<(usize, usize)> {
+    // networks either nest or are disjoint: sorted by address then size, each
+    // network overlaps exactly the networks of the stack that still contain it
+    let mut order: Vec<usize> = (0..cidrs.len()).collect();
+    order.sort_by_key(|index| {
+        let network = cidrs[*index].network_address();
+        (network.ip().to_u128(), network.mask().prefix_length())
+    });
+
+    let mut pairs = Vec::new();
+    let mut containing: Vec<usize> = Vec::new();
+    for index in order {
+        while let Some(last) = containing.last() {
+            if cidrs[*last].contains_cidr(&cidrs[index]) {
+                break;
+            }
+            containing.pop();
+        }
+
+        pairs.extend(
+            containing
+                .iter()
+                .map(|other| (index.min(*other), index.max(*other))),
+        );
+        containing.push(index);
+    }
+
+    pairs.sort_unstable();
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::overlapping_pairs;
+    use crate::net::{cidrs, IPv4, IPv6};
+
+    #[test]
+    fn overlapping_pairs_none() {
+        assert!(overlapping_pairs::<IPv4>(&[]).is_empty());
+        assert!(overlapping_pairs(&cidrs::<IPv4>(&[
+            "10.0.0.0/24",
+            "10.0.1.0/24",
+            "192.168.0.0/16"
+        ]))
+        .is_empty());
+    }
+
+    #[test]
+    fn overlapping_pairs_nested() {
+        let input = cidrs::<IPv4>(&[
+            "10.0.4.0/24",
+            "10.0.0.0/16",
+            "172.16.0.0/12",
+            "10.0.4.128/25",
+            "10.0.5.0/24",
+        ]);
+
+        assert_eq!(
+            vec![(0, 1), (0, 3), (1, 3), (1, 4)],
+            overlapping_pairs(&input)
+        );
+    }
+
+    #[test]
+    fn overlapping_pairs_duplicates_and_hosts() {
+        let input = cidrs::<IPv4>(&["10.0.0.0/24", "10.0.0.42/24", "10.0.0.0/24"]);
+
+        assert_eq!(vec![(0, 1), (0, 2), (1, 2)], overlapping_pairs(&input));
+    }
+
+    #[test]
+    fn overlapping_pairs_ipv6() {
+        let input = cidrs::<IPv6>(&["::/0", "2001:db8::/32", "2001:db9::/32"]);
+
+        assert_eq!(vec![(0, 1), (0, 2)], overlapping_pairs(&input));
+    }
+}
@@ -0,0 +1,198 @@
+use crate::net::AddressFamily;
+use crate::net::IPSet;
+use crate::net::IPv4;
+use crate::net::Mask;
+use crate::net::CIDR;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum PlanningError {
+    /// The named requirement doesn't fit in the space left.
+    DoesNotFit(String),
+}
+
+/// Named number of usable hosts a subnet has to provide.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct SubnetRequirement {
+    pub name: String,
+    pub hosts: u128,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Allocation<A: AddressFamily = IPv4> {
+    pub name: String,
+    pub hosts: u128,
+    pub cidr: CIDR<A>,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Plan<A: AddressFamily = IPv4> {
+    /// Allocated subnets, in ascending address order.
+    pub allocations: Vec<Allocation<A>>,
+    pub free: IPSet<A>,
+}
+
+/// Allocates a subnet with the smallest mask providing enough hosts for each
+/// requirement. Subnets are allocated largest first at the lowest free
+/// address, which never fragments the free space.
+pub fn plan<A: AddressFamily>(
+    supernet: &CIDR<A>,
+    requirements: &[SubnetRequirement],
+) -> Result<Plan<A>, PlanningError> {
+    let mut masks = requirements
+        .iter()
+        .map(|requirement| {
+            Mask::<A>::for_hosts(requirement.hosts)
+                .map(|mask| (requirement, mask))
+                .ok_or_else(|| PlanningError::DoesNotFit(requirement.name.clone()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    // stable, so requirements of the same size keep their order
+    masks.sort_by_key(|(_, mask)| mask.prefix_length());
+
+    let mut free = IPSet::from(supernet.network_address());
+    let mut allocations = Vec::new();
+    for (requirement, mask) in masks {
+        let cidr = free
+            .cidrs()
+            .find(|block| block.mask() <= mask)
+            .map(|block| CIDR::new(block.ip(), mask))
+            .ok_or_else(|| PlanningError::DoesNotFit(requirement.name.clone()))?;
+
+        free.remove(cidr);
+        allocations.push(Allocation {
+            name: requirement.name.clone(),
+            hosts: requirement.hosts,
+            cidr,
+        });
+    }
+    allocations.sort_by_key(|allocation| allocation.cidr.ip());
+
+    Ok(Plan { allocations, free })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{plan, Allocation, PlanningError, SubnetRequirement};
+    use crate::net::{IPv4, IPv6, CIDR};
+
+    fn requirements(raw: &[(&str, u128)]) -> Vec<SubnetRequirement> {
+        raw.iter()
+            .map(|(name, hosts)| SubnetRequirement {
+                name: name.to_string(),
+                hosts: *hosts,
+            })
+            .collect()
+    }
+
+    fn allocations(plan: &[Allocation<IPv4>]) -> Vec<(&str, String)> {
+        plan.iter()
+            .map(|allocation| (allocation.name.as_str(), allocation.cidr.to_string()))
+            .collect()
+    }
+
+    #[test]
+    fn plan_largest_first() {
+        let supernet = "10.0.0.0/22".parse::<CIDR>().unwrap();
+        let plan = plan(
+            &supernet,
+            &requirements(&[
+                ("mgmt", 30),
+                ("users", 500),
+                ("p2p 1", 2),
+                ("voip", 200),
+                ("p2p 2", 2),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("users", "10.0.0.0/23".to_string()),
+                ("voip", "10.0.2.0/24".to_string()),
+                ("mgmt", "10.0.3.0/27".to_string()),
+                ("p2p 1", "10.0.3.32/31".to_string()),
+                ("p2p 2", "10.0.3.34/31".to_string()),
+            ],
+            allocations(&plan.allocations)
+        );
+        assert_eq!(
+            vec![
+                "10.0.3.36/30",
+                "10.0.3.40/29",
+                "10.0.3.48/28",
+                "10.0.3.64/26",
+                "10.0.3.128/25"
+            ],
+            plan.free
+                .cidrs()
+                .map(|cidr| cidr.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn plan_exact_fit() {
+        let supernet = "192.168.0.0/24".parse::<CIDR>().unwrap();
+        let plan = plan(
+            &supernet,
+            &requirements(&[("a", 126), ("b", 62), ("c", 62)]),
+        )
+        .unwrap();
+
+        assert_eq!(
+            vec![
+                ("a", "192.168.0.0/25".to_string()),
+                ("b", "192.168.0.128/26".to_string()),
+                ("c", "192.168.0.192/26".to_string()),
+            ],
+            allocations(&plan.allocations)
+        );
+        assert!(plan.free.is_empty());
+    }
+
+    #[test]
+    fn plan_does_not_fit() {
+        let supernet = "192.168.0.0/24".parse::<CIDR>().unwrap();
+
+        // c needs a /25 so it is allocated before b, which is left without space
+        assert_eq!(
+            Err(PlanningError::DoesNotFit("b".to_string())),
+            plan(
+                &supernet,
+                &requirements(&[("a", 126), ("b", 62), ("c", 63)])
+            )
+        );
+        assert_eq!(
+            Err(PlanningError::DoesNotFit("huge".to_string())),
+            plan(&supernet, &requirements(&[("huge", 300)]))
+        );
+        assert_eq!(
+            Err(PlanningError::DoesNotFit("huge".to_string())),
+            plan(&supernet, &requirements(&[("huge", 1 << 40)]))
+        );
+    }
+
+    #[test]
+    fn plan_ipv6() {
+        let supernet = "2001:db8::/62".parse::<CIDR<IPv6>>().unwrap();
+        let plan = plan(
+            &supernet,
+            &requirements(&[("lan", 1 << 64), ("servers", 1 << 63), ("loopback", 1)]),
+        )
+        .unwrap();
+
+        let allocated: Vec<String> = plan
+            .allocations
+            .iter()
+            .map(|allocation| allocation.cidr.to_string())
+            .collect();
+        assert_eq!(
+            vec![
+                "2001:db8::/64",
+                "2001:db8:0:1::/65",
+                "2001:db8:0:1:8000::/128"
+            ],
+            allocated
+        );
+    }
+}
@@ -0,0 +1,391 @@
+use crate::net::AddressFamily;
+use crate::net::IPv4;
+use crate::net::Mask;
+use crate::net::CIDR;
+
+/// Binary trie mapping CIDRs to values, where each level matches one more
+/// bit of the network address. CIDRs are stored as their network address.
+#[derive(Debug, Clone)]
+pub struct PrefixTrie<V, A: AddressFamily = IPv4> {
+    root: Node<V>,
+    len: usize,
+    family: std::marker::PhantomData<A>,
+}
+
+#[derive(Debug, Clone)]
+struct Node<V> {
+    value: Option<V>,
+    children: [Option<Box<Node<V>>>; 2],
+}
+
+impl<V> Node<V> {
+    fn new() -> Self {
+        Self {
+            value: None,
+            children: [None, None],
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.value.is_none() && self.children.iter().all(Option::is_none)
+    }
+}
+
+impl<V, A: AddressFamily> Default for PrefixTrie<V, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V, A: AddressFamily> FromIterator<(CIDR<A>, V)> for PrefixTrie<V, A> {
+    fn from_iter<T: IntoIterator<Item = (CIDR<A>, V)>>(iter: T) -> Self {
+        let mut trie = Self::new();
+        for (cidr, value) in iter {
+            trie.insert(cidr, value);
+        }
+
+        trie
+    }
+}
+
+// bit of the address at the given depth, starting from the most significant
+fn bit<A: AddressFamily>(address: u128, depth: u8) -> usize {
+    (address >> (A::BITS - 1 - depth) & 1) as usize
+}
+
+impl<V, A: AddressFamily> PrefixTrie<V, A> {
+    pub fn new() -> Self {
+        Self {
+            root: Node::new(),
+            len: 0,
+            family: std::marker::PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the value previously associated with the CIDR network.
+    pub fn insert(&mut self, cidr: CIDR<A>, value: V) -> Option<V> {
+        let address = cidr.network_address().ip().to_u128();
+
+        let mut node = &mut self.root;
+        for depth in 0..cidr.mask().prefix_length() {
+            node = node.children[bit::<A>(address, depth)]
+                .get_or_insert_with(|| Box::new(Node::new()));
+        }
+
+        let previous = node.value.replace(value);
+        if previous.is_none() {
+            self.len += 1;
+        }
+
+        previous
+    }
+
+    pub fn remove(&mut self, cidr: &CIDR<A>) -> Option<V> {
+        let address = cidr.network_address().ip().to_u128();
+        let removed = Self::remove_from(&mut self.root, address, 0, cidr.mask().prefix_length());
+        if removed.is_some() {
+            self.len -= 1;
+        }
+
+        removed
+    }
+
+    // Removes the value and prunes the branches left empty.
+    fn remove_from(node: &mut Node<V>, address: u128, depth: u8, prefix_length: u8) -> Option<V> {
+        if depth == prefix_length {
+            return node.value.take();
+        }
+
+        let child = &mut node.children[bit::<A>(address, depth)];
+        let removed = Self::remove_from(child.as_mut()?, address, depth + 1, prefix_length);
+        if child.as_ref().is_some_and(|child| child.is_empty()) {
+            *child = None;
+        }
+
+        removed
+    }
+
+    fn node(&self, cidr: &CIDR<A>) -> Option<&Node<V>> {
+        let address = cidr.network_address().ip().to_u128();
+
+        let mut node = &self.root;
+        for depth in 0..cidr.mask().prefix_length() {
+            node = node.children[bit::<A>(address, depth)].as_ref()?;
+        }
+
+        Some(node)
+    }
+
+    /// Value associated with exactly the CIDR network.
+    pub fn get(&self, cidr: &CIDR<A>) -> Option<&V> {
+        self.node(cidr)?.value.as_ref()
+    }
+
+    /// Every entry containing the CIDR, itself included, from the least to
+    /// the most specific.
+    pub fn covering(&self, cidr: &CIDR<A>) -> impl Iterator<Item = (CIDR<A>, &V)> {
+        let address = cidr.network_address().ip().to_u128();
+        let prefix_length = cidr.mask().prefix_length();
+        let mut next = Some((&self.root, 0u8));
+
+        std::iter::from_fn(move || loop {
+            let (node, depth) = next?;
+            next = if depth < prefix_length {
+                node.children[bit::<A>(address, depth)]
+                    .as_deref()
+                    .map(|child| (child, depth + 1))
+            } else {
+                None
+            };
+
+            if let Some(value) = &node.value {
+                let mask = Mask::new(depth).unwrap();
+                return Some((
+                    CIDR::new(A::from_u128(address), mask).network_address(),
+                    value,
+                ));
+            }
+        })
+    }
+
+    /// Every entry containing the address, from the least to the most
+    /// specific.
+    pub fn matches(&self, ip: &A) -> impl Iterator<Item = (CIDR<A>, &V)> {
+        self.covering(&CIDR::new(*ip, Mask::new(A::BITS).unwrap()))
+    }
+
+    /// Most specific entry containing the address.
+    pub fn longest_match(&self, ip: &A) -> Option<(CIDR<A>, &V)> {
+        self.matches(ip).last()
+    }
+
+    /// Every entry within the CIDR, itself included, in ascending address
+    /// order with less specific entries first.
+    pub fn subtree(&self, cidr: &CIDR<A>) -> Subtree<'_, V, A> {
+        let network = cidr.network_address();
+        let stack = self
+            .node(&network)
+            .map(|node| (node, network.ip().to_u128(), network.mask().prefix_length()))
+            .into_iter()
+            .collect();
+
+        Subtree {
+            stack,
+            family: std::marker::PhantomData,
+        }
+    }
+
+    /// Every entry, in ascending address order with less specific entries
+    /// first.
+    pub fn iter(&self) -> Subtree<'_, V, A> {
+        self.subtree(&CIDR::new(A::from_u128(0), Mask::new(0).unwrap()))
+    }
+}
+
+/// Depth-first iterator over the entries of a `PrefixTrie`.
+pub struct Subtree<'a, V, A: AddressFamily> {
+    stack: Vec<(&'a Node<V>, u128, u8)>,
+    family: std::marker::PhantomData<A>,
+}
+
+impl<'a, V, A: AddressFamily> Iterator for Subtree<'a, V, A> {
+    type Item = (CIDR<A>, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some((node, address, depth)) = self.stack.pop() {
+            // pushed in reverse so that the 0 branch is visited first
+            for (bit, child) in node.children.iter().enumerate().rev() {
+                if let Some(child) = child {
+                    let child_address = address | (bit as u128) << (A::BITS - 1 - depth);
+                    self.stack.push((child, child_address, depth + 1));
+                }
+            }
+
+            if let Some(value) = &node.value {
+                let cidr = CIDR::new(A::from_u128(address), Mask::new(depth).unwrap());
+                return Some((cidr, value));
+            }
+        }
+
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::PrefixTrie;
+    use crate::net::{AddressFamily, IPv4, IPv6, CIDR};
+
+    fn cidr<A: AddressFamily>(raw: &str) -> CIDR<A> {
+        raw.parse().unwrap()
+    }
+
+    fn table() -> PrefixTrie<&'static str> {
+        [
+            ("0.0.0.0/0", "default"),
+            ("10.0.0.0/8", "corporate"),
+            ("10.1.0.0/16", "datacenter"),
+            ("10.1.42.0/24", "storage"),
+            ("10.1.42.7/32", "nas"),
+            ("192.168.0.0/16", "lab"),
+        ]
+        .into_iter()
+        .map(|(raw, value)| (cidr(raw), value))
+        .collect()
+    }
+
+    #[test]
+    fn insert_get() {
+        let mut trie = PrefixTrie::<u32>::new();
+        assert!(trie.is_empty());
+
+        assert_eq!(None, trie.insert(cidr("10.0.0.0/8"), 1));
+        assert_eq!(None, trie.insert(cidr("10.1.0.0/16"), 2));
+        assert_eq!(Some(1), trie.insert(cidr("10.42.0.1/8"), 3));
+        assert_eq!(2, trie.len());
+
+        assert_eq!(Some(&3), trie.get(&cidr("10.0.0.0/8")));
+        assert_eq!(Some(&2), trie.get(&cidr("10.1.2.3/16")));
+        assert_eq!(None, trie.get(&cidr("10.0.0.0/16")));
+        assert_eq!(None, trie.get(&cidr("10.1.0.0/24")));
+    }
+
+    #[test]
+    fn remove() {
+        let mut trie = table();
+
+        assert_eq!(Some("datacenter"), trie.remove(&cidr("10.1.0.0/16")));
+        assert_eq!(None, trie.remove(&cidr("10.1.0.0/16")));
+        assert_eq!(None, trie.remove(&cidr("172.16.0.0/12")));
+        assert_eq!(5, trie.len());
+        assert_eq!(Some(&"storage"), trie.get(&cidr("10.1.42.0/24")));
+
+        for (cidr, _) in table().iter() {
+            trie.remove(&cidr);
+        }
+        assert!(trie.is_empty());
+        assert!(trie.root.is_empty(), "empty branches should be pruned");
+    }
+
+    #[test]
+    fn longest_match() {
+        let trie = table();
+
+        assert_eq!(
+            Some((cidr("10.1.42.7/32"), &"nas")),
+            trie.longest_match(&IPv4::new(10, 1, 42, 7))
+        );
+        assert_eq!(
+            Some((cidr("10.1.42.0/24"), &"storage")),
+            trie.longest_match(&IPv4::new(10, 1, 42, 8))
+        );
+        assert_eq!(
+            Some((cidr("10.0.0.0/8"), &"corporate")),
+            trie.longest_match(&IPv4::new(10, 2, 0, 1))
+        );
+        assert_eq!(
+            Some((cidr("0.0.0.0/0"), &"default")),
+            trie.longest_match(&IPv4::new(8, 8, 8, 8))
+        );
+
+        let mut trie = table();
+        trie.remove(&cidr("0.0.0.0/0"));
+        assert_eq!(None, trie.longest_match(&IPv4::new(8, 8, 8, 8)));
+    }
+
+    #[test]
+    fn matches() {
+        let trie = table();
+
+        assert_eq!(
+            vec![
+                (cidr("0.0.0.0/0"), &"default"),
+                (cidr("10.0.0.0/8"), &"corporate"),
+                (cidr("10.1.0.0/16"), &"datacenter"),
+                (cidr("10.1.42.0/24"), &"storage"),
+            ],
+            trie.matches(&IPv4::new(10, 1, 42, 8)).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn covering() {
+        let trie = table();
+
+        assert_eq!(
+            vec![
+                (cidr("0.0.0.0/0"), &"default"),
+                (cidr("10.0.0.0/8"), &"corporate"),
+                (cidr("10.1.0.0/16"), &"datacenter"),
+            ],
+            trie.covering(&cidr("10.1.0.0/16")).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn subtree() {
+        let trie = table();
+
+        assert_eq!(
+            vec![
+                (cidr("10.1.0.0/16"), &"datacenter"),
+                (cidr("10.1.42.0/24"), &"storage"),
+                (cidr("10.1.42.7/32"), &"nas"),
+            ],
+            trie.subtree(&cidr("10.1.0.0/16")).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec![
+                (cidr("10.1.42.0/24"), &"storage"),
+                (cidr("10.1.42.7/32"), &"nas"),
+            ],
+            trie.subtree(&cidr("10.1.32.0/19")).collect::<Vec<_>>()
+        );
+        assert_eq!(0, trie.subtree(&cidr("172.16.0.0/12")).count());
+    }
+
+    #[test]
+    fn iter() {
+        let trie = table();
+
+        assert_eq!(
+            vec![
+                "0.0.0.0/0",
+                "10.0.0.0/8",
+                "10.1.0.0/16",
+                "10.1.42.0/24",
+                "10.1.42.7/32",
+                "192.168.0.0/16",
+            ],
+            trie.iter()
+                .map(|(cidr, _)| cidr.to_string())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn ipv6() {
+        let mut trie = PrefixTrie::<&str, IPv6>::new();
+        trie.insert(cidr("2001:db8::/32"), "documentation");
+        trie.insert(cidr("2001:db8:1::/48"), "site");
+        trie.insert(cidr("::1/128"), "loopback");
+
+        assert_eq!(
+            Some((cidr("2001:db8:1::/48"), &"site")),
+            trie.longest_match(&"2001:db8:1::42".parse().unwrap())
+        );
+        assert_eq!(
+            Some((cidr("::1/128"), &"loopback")),
+            trie.longest_match(&"::1".parse().unwrap())
+        );
+        assert_eq!(None, trie.longest_match(&"::2".parse().unwrap()));
+    }
+}
@@ -0,0 +1,178 @@
+use crate::net::AddressFamily;
+use crate::net::CIDRComparison;
+use crate::net::IPKind;
+use crate::net::Mask;
+use crate::net::CIDR;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+/// Entry of the IANA IPv4/IPv6 special-purpose address registries (RFC 6890).
+#[derive(Debug, PartialEq, Eq)]
+pub struct SpecialPurposeBlock {
+    pub address: u128,
+    pub prefix_length: u8,
+    pub name: &'static str,
+    pub rfc: &'static str,
+    pub source: bool,
+    pub destination: bool,
+    pub forwardable: bool,
+    pub globally_reachable: bool,
+    pub private: bool,
+    /// Text of `IPKind::Special`, the registry name unless the block had a
+    /// kind before the registry was used.
+    pub label: &'static str,
+    /// Name of the block in the `ADDRSPACE` of Red Hat ipcalc, `None` when
+    /// it doesn't distinguish the block from the enclosing one.
+    pub address_space: Option<&'static str>,
+}
+
+impl SpecialPurposeBlock {
+    pub fn network<A: AddressFamily>(&self) -> CIDR<A> {
+        CIDR::new(
+            A::from_u128(self.address),
+            Mask::new(self.prefix_length).unwrap(),
+        )
+    }
+
+    pub fn contains<A: AddressFamily>(&self, address: &A) -> bool {
+        let mask = Mask::<A>::new(self.prefix_length).unwrap();
+
+        mask.network_address(address).to_u128() == self.address
+    }
+
+    pub fn kind(&self) -> IPKind {
+        if self.private {
+            IPKind::Private
+        } else {
+            IPKind::Special(self.label)
+        }
+    }
+}
+
+/// Registry entry overlapping a CIDR. `relation` is the relation of the CIDR
+/// to the block: `Subset` when the CIDR is within the block, `Superset` when
+/// it contains it.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SpecialPurposeOverlap {
+    pub block: &'static SpecialPurposeBlock,
+    pub relation: CIDRComparison,
+}
+
+const fn v4(a: u8, b: u8, c: u8, d: u8) -> u128 {
+    Ipv4Addr::new(a, b, c, d).to_bits() as u128
+}
+
+#[allow(clippy::too_many_arguments)]
+const fn v6(a: u16, b: u16, c: u16, d: u16, e: u16, f: u16, g: u16, h: u16) -> u128 {
+    Ipv6Addr::new(a, b, c, d, e, f, g, h).to_bits()
+}
+
+const fn block(
+    address: u128,
+    prefix_length: u8,
+    name: &'static str,
+    rfc: &'static str,
+    [source, destination, forwardable, globally_reachable]: [bool; 4],
+) -> SpecialPurposeBlock {
+    SpecialPurposeBlock {
+        address,
+        prefix_length,
+        name,
+        rfc,
+        source,
+        destination,
+        forwardable,
+        globally_reachable,
+        private: false,
+        label: name,
+        address_space: None,
+    }
+}
+
+// Keeps the kind displayed for the block before the registry was used.
+const fn labelled(label: &'static str, block: SpecialPurposeBlock) -> SpecialPurposeBlock {
+    SpecialPurposeBlock { label, ..block }
+}
+
+const fn address_space(
+    address_space: &'static str,
+    block: SpecialPurposeBlock,
+) -> SpecialPurposeBlock {
+    SpecialPurposeBlock {
+        address_space: Some(address_space),
+        ..block
+    }
+}
+
+const fn private(block: SpecialPurposeBlock) -> SpecialPurposeBlock {
+    SpecialPurposeBlock {
+        private: true,
+        ..block
+    }
+}
+
+// flags are [source, destination, forwardable, globally reachable]
+const T: bool = true;
+const F: bool = false;
+
+/// https://www.iana.org/assignments/iana-ipv4-special-registry, completed
+/// with the multicast range (RFC 5771).
+#[rustfmt::skip]
+pub static IPV4_SPECIAL_PURPOSE: [SpecialPurposeBlock; 26] = [
+    address_space("This host on this network", block(v4(0, 0, 0, 0), 8, "This network", "RFC 791", [T, F, F, F])),
+    block(v4(0, 0, 0, 0), 32, "This host on this network", "RFC 1122", [T, F, F, F]),
+    address_space("Private Use", private(block(v4(10, 0, 0, 0), 8, "Private-Use", "RFC 1918", [T, T, T, F]))),
+    address_space("Shared Address Space", block(v4(100, 64, 0, 0), 10, "Shared Address Space", "RFC 6598", [T, T, T, F])),
+    address_space("Loopback", labelled("localhost", block(v4(127, 0, 0, 0), 8, "Loopback", "RFC 1122", [F, F, F, F]))),
+    address_space("Link Local", labelled("link-local", block(v4(169, 254, 0, 0), 16, "Link Local", "RFC 3927", [T, T, F, F]))),
+    address_space("Private Use", private(block(v4(172, 16, 0, 0), 12, "Private-Use", "RFC 1918", [T, T, T, F]))),
+    address_space("IETF Protocol Assignments", block(v4(192, 0, 0, 0), 24, "IETF Protocol Assignments", "RFC 6890", [F, F, F, F])),
+    address_space("IPv4 Service Continuity Prefix", block(v4(192, 0, 0, 0), 29, "IPv4 Service Continuity Prefix", "RFC 7335", [T, T, T, F])),
+    block(v4(192, 0, 0, 8), 32, "IPv4 dummy address", "RFC 7600", [T, F, F, F]),
+    block(v4(192, 0, 0, 9), 32, "Port Control Protocol Anycast", "RFC 7723", [T, T, T, T]),
+    block(v4(192, 0, 0, 10), 32, "Traversal Using Relays around NAT Anycast", "RFC 8155", [T, T, T, T]),
+    block(v4(192, 0, 0, 170), 32, "NAT64/DNS64 Discovery", "RFC 8880", [F, F, F, F]),
+    block(v4(192, 0, 0, 171), 32, "NAT64/DNS64 Discovery", "RFC 8880", [F, F, F, F]),
+    address_space("Documentation (TEST-NET-1)", labelled("documentation", block(v4(192, 0, 2, 0), 24, "Documentation", "RFC 5737", [F, F, F, F]))),
+    block(v4(192, 31, 196, 0), 24, "AS112-v4", "RFC 7535", [T, T, T, T]),
+    block(v4(192, 52, 193, 0), 24, "AMT", "RFC 7450", [T, T, T, T]),
+    address_space("6 to 4 Relay Anycast (Deprecated)", block(v4(192, 88, 99, 0), 24, "Deprecated (6to4 Relay Anycast)", "RFC 7526", [F, F, F, F])),
+    address_space("Private Use", private(block(v4(192, 168, 0, 0), 16, "Private-Use", "RFC 1918", [T, T, T, F]))),
+    block(v4(192, 175, 48, 0), 24, "Direct Delegation AS112 Service", "RFC 7534", [T, T, T, T]),
+    address_space("Benchmarking", block(v4(198, 18, 0, 0), 15, "Benchmarking", "RFC 2544", [T, T, T, F])),
+    address_space("Documentation (TEST-NET-2)", labelled("documentation", block(v4(198, 51, 100, 0), 24, "Documentation", "RFC 5737", [F, F, F, F]))),
+    address_space("Documentation (TEST-NET-3)", labelled("documentation", block(v4(203, 0, 113, 0), 24, "Documentation", "RFC 5737", [F, F, F, F]))),
+    address_space("Multicast", labelled("multicast", block(v4(224, 0, 0, 0), 4, "Multicast", "RFC 5771", [F, T, T, F]))),
+    address_space("Reserved", block(v4(240, 0, 0, 0), 4, "Reserved", "RFC 1112", [F, F, F, F])),
+    address_space("Limited Broadcast", block(v4(255, 255, 255, 255), 32, "Limited Broadcast", "RFC 919", [F, T, F, F])),
+];
+
+/// https://www.iana.org/assignments/iana-ipv6-special-registry, completed
+/// with the multicast range (RFC 4291).
+#[rustfmt::skip]
+pub static IPV6_SPECIAL_PURPOSE: [SpecialPurposeBlock; 25] = [
+    address_space("Loopback Address", labelled("localhost", block(v6(0, 0, 0, 0, 0, 0, 0, 1), 128, "Loopback Address", "RFC 4291", [F, F, F, F]))),
+    address_space("Unspecified Address", block(v6(0, 0, 0, 0, 0, 0, 0, 0), 128, "Unspecified Address", "RFC 4291", [T, F, F, F])),
+    address_space("IPv4-mapped Address", block(v6(0, 0, 0, 0, 0, 0xffff, 0, 0), 96, "IPv4-mapped Address", "RFC 4291", [F, F, F, F])),
+    address_space("IPv4-IPv6 Translat.", block(v6(0x64, 0xff9b, 0, 0, 0, 0, 0, 0), 96, "IPv4-IPv6 Translation", "RFC 6052", [T, T, T, T])),
+    block(v6(0x64, 0xff9b, 1, 0, 0, 0, 0, 0), 48, "IPv4-IPv6 Translation", "RFC 8215", [T, T, T, F]),
+    address_space("Discard-Only Address Block", block(v6(0x100, 0, 0, 0, 0, 0, 0, 0), 64, "Discard-Only Address Block", "RFC 6666", [T, T, T, F])),
+    address_space("IETF Protocol Assignments", block(v6(0x2001, 0, 0, 0, 0, 0, 0, 0), 23, "IETF Protocol Assignments", "RFC 2928", [F, F, F, F])),
+    address_space("TEREDO", block(v6(0x2001, 0, 0, 0, 0, 0, 0, 0), 32, "TEREDO", "RFC 4380", [T, T, T, F])),
+    block(v6(0x2001, 1, 0, 0, 0, 0, 0, 1), 128, "Port Control Protocol Anycast", "RFC 7723", [T, T, T, T]),
+    block(v6(0x2001, 1, 0, 0, 0, 0, 0, 2), 128, "Traversal Using Relays around NAT Anycast", "RFC 8155", [T, T, T, T]),
+    block(v6(0x2001, 1, 0, 0, 0, 0, 0, 3), 128, "DNS-SD Service Registration Protocol Anycast", "RFC 9665", [T, T, T, T]),
+    address_space("Benchmarking", block(v6(0x2001, 2, 0, 0, 0, 0, 0, 0), 48, "Benchmarking", "RFC 5180", [T, T, T, F])),
+    block(v6(0x2001, 3, 0, 0, 0, 0, 0, 0), 32, "AMT", "RFC 7450", [T, T, T, T]),
+    block(v6(0x2001, 4, 0x112, 0, 0, 0, 0, 0), 48, "AS112-v6", "RFC 7535", [T, T, T, T]),
+    address_space("Deprecated (previously ORCHID)", block(v6(0x2001, 0x10, 0, 0, 0, 0, 0, 0), 28, "Deprecated (previously ORCHID)", "RFC 4843", [F, F, F, F])),
+    address_space("ORCHIDv2", block(v6(0x2001, 0x20, 0, 0, 0, 0, 0, 0), 28, "ORCHIDv2", "RFC 7343", [T, T, T, T])),
+    block(v6(0x2001, 0x30, 0, 0, 0, 0, 0, 0), 28, "Drone Remote ID Protocol Entity Tags (DETs) Prefix", "RFC 9374", [T, T, T, T]),
+    address_space("Documentation", labelled("documentation", block(v6(0x2001, 0xdb8, 0, 0, 0, 0, 0, 0), 32, "Documentation", "RFC 3849", [F, F, F, F]))),
+    address_space("6to4", block(v6(0x2002, 0, 0, 0, 0, 0, 0, 0), 16, "6to4", "RFC 3056", [T, T, T, F])),
+    block(v6(0x2620, 0x4f, 0x8000, 0, 0, 0, 0, 0), 48, "Direct Delegation AS112 Service", "RFC 7534", [T, T, T, T]),
+    labelled("documentation", block(v6(0x3fff, 0, 0, 0, 0, 0, 0, 0), 20, "Documentation", "RFC 9637", [F, F, F, F])),
+    block(v6(0x5f00, 0, 0, 0, 0, 0, 0, 0), 16, "Segment Routing (SRv6) SIDs", "RFC 9602", [T, T, T, F]),
+    address_space("Unique Local Unicast", private(block(v6(0xfc00, 0, 0, 0, 0, 0, 0, 0), 7, "Unique-Local", "RFC 4193", [T, T, T, F]))),
+    address_space("Link-Scoped Unicast", labelled("link-local", block(v6(0xfe80, 0, 0, 0, 0, 0, 0, 0), 10, "Link-Local Unicast", "RFC 4291", [T, T, F, F]))),
+    address_space("Multicast", labelled("multicast", block(v6(0xff00, 0, 0, 0, 0, 0, 0, 0), 8, "Multicast", "RFC 4291", [F, T, T, F]))),
+];
@@ -0,0 +1,197 @@
+use crate::net::AddressFamily;
+use crate::net::IPRange;
+use crate::net::IPSet;
+use crate::net::CIDR;
+
+/// Smallest set of CIDRs covering exactly the same addresses as `cidrs`, in
+/// ascending order. Overlapping and adjacent networks are merged, host CIDRs
+/// are replaced by their network.
+pub fn summarize<A: AddressFamily>(cidrs: &[CIDR<A>]) -> Vec<CIDR<A>> {
+    cidrs
+        .iter()
+        .copied()
+        .collect::<IPSet<A>>()
+        .cidrs()
+        .collect()
+}
+
+/// Same as `summarize`, then replaces neighbouring networks by their common
+/// supernet as long as the addresses covered in excess don't exceed
+/// `max_extra_addresses` in total. Cheapest merges are done first.
+pub fn summarize_with_overcoverage<A: AddressFamily>(
+    cidrs: &[CIDR<A>],
+    max_extra_addresses: u128,
+) -> Vec<CIDR<A>> {
+    let mut summary = summarize(cidrs);
+    let mut budget = max_extra_addresses;
+
+    loop {
+        let cheapest = (1..summary.len())
+            .map(|index| merge_candidate(&summary, index - 1, index))
+            .min_by_key(|candidate| candidate.extra_addresses);
+
+        match cheapest {
+            Some(candidate) if candidate.extra_addresses <= budget => {
+                budget -= candidate.extra_addresses;
+                summary.splice(candidate.start..=candidate.end, [candidate.supernet]);
+            }
+            _ => return summary,
+        }
+    }
+}
+
+struct MergeCandidate<A: AddressFamily> {
+    supernet: CIDR<A>,
+    // bounds of the summary entries covered by the supernet
+    start: usize,
+    end: usize,
+    extra_addresses: u128,
+}
+
+// Merges the two neighbouring entries of a sorted, disjoint summary into
+// their common supernet, along with the surrounding entries it covers.
+fn merge_candidate<A: AddressFamily>(
+    summary: &[CIDR<A>],
+    left: usize,
+    right: usize,
+) -> MergeCandidate<A> {
+    let supernet = summary[left].common_supernet(&summary[right]);
+
+    let mut start = left;
+    while start > 0 && supernet.contains_cidr(&summary[start - 1]) {
+        start -= 1;
+    }
+    let mut end = right;
+    while end + 1 < summary.len() && supernet.contains_cidr(&summary[end + 1]) {
+        end += 1;
+    }
+
+    // count the gaps between covered entries to avoid overflowing on large
+    // IPv6 networks
+    let supernet_range = IPRange::from(supernet);
+    let mut extra_addresses = 0;
+    let mut next = Some(supernet_range.first().to_u128());
+    for cidr in &summary[start..=end] {
+        let range = IPRange::from(*cidr);
+        if let Some(next) = next {
+            extra_addresses += range.first().to_u128() - next;
+        }
+        next = range.last().to_u128().checked_add(1);
+    }
+    if let Some(next) = next {
+        let last = supernet_range.last().to_u128();
+        if next <= last {
+            extra_addresses += last - next + 1;
+        }
+    }
+
+    MergeCandidate {
+        supernet,
+        start,
+        end,
+        extra_addresses,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{summarize, summarize_with_overcoverage};
+    use crate::net::{cidrs, IPv4, IPv6, CIDR};
+
+    #[test]
+    fn summarize_empty() {
+        assert_eq!(Vec::<CIDR>::new(), summarize::<IPv4>(&[]));
+    }
+
+    #[test]
+    fn summarize_adjacent() {
+        let input = cidrs::<IPv4>(&["10.0.1.0/24", "10.0.0.0/24", "10.0.2.0/23"]);
+
+        assert_eq!(cidrs::<IPv4>(&["10.0.0.0/22"]), summarize(&input));
+    }
+
+    #[test]
+    fn summarize_overlapping() {
+        let input = cidrs::<IPv4>(&[
+            "10.0.0.0/16",
+            "10.0.42.0/24",
+            "10.0.255.255/32",
+            "10.0.0.0/16",
+        ]);
+
+        assert_eq!(cidrs::<IPv4>(&["10.0.0.0/16"]), summarize(&input));
+    }
+
+    #[test]
+    fn summarize_unaligned() {
+        let input = cidrs::<IPv4>(&["10.0.1.0/24", "10.0.2.0/24", "192.168.0.12/30"]);
+        let expected = cidrs::<IPv4>(&["10.0.1.0/24", "10.0.2.0/24", "192.168.0.12/30"]);
+
+        assert_eq!(expected, summarize(&input));
+    }
+
+    #[test]
+    fn summarize_host_cidrs() {
+        let input = cidrs::<IPv4>(&["10.0.0.42/31", "10.0.0.40/31", "10.0.0.44/30"]);
+
+        assert_eq!(cidrs::<IPv4>(&["10.0.0.40/29"]), summarize(&input));
+    }
+
+    #[test]
+    fn summarize_whole_address_space() {
+        let input = cidrs::<IPv4>(&["0.0.0.0/1", "128.0.0.0/1", "255.255.255.255/32"]);
+        assert_eq!(cidrs::<IPv4>(&["0.0.0.0/0"]), summarize(&input));
+
+        let input = cidrs::<IPv6>(&["::/1", "8000::/1"]);
+        assert_eq!(cidrs::<IPv6>(&["::/0"]), summarize(&input));
+    }
+
+    #[test]
+    fn summarize_ipv6() {
+        let input = cidrs::<IPv6>(&["2001:db8::/48", "2001:db8:1::/48", "2001:db8:3::/48"]);
+        let expected = cidrs::<IPv6>(&["2001:db8::/47", "2001:db8:3::/48"]);
+
+        assert_eq!(expected, summarize(&input));
+    }
+
+    #[test]
+    fn overcoverage_within_budget() {
+        let input = cidrs::<IPv4>(&["10.0.0.0/24", "10.0.1.0/24", "10.0.3.0/24"]);
+
+        assert_eq!(
+            cidrs::<IPv4>(&["10.0.0.0/23", "10.0.3.0/24"]),
+            summarize_with_overcoverage(&input, 255)
+        );
+        assert_eq!(
+            cidrs::<IPv4>(&["10.0.0.0/22"]),
+            summarize_with_overcoverage(&input, 256)
+        );
+    }
+
+    #[test]
+    fn overcoverage_cheapest_first() {
+        let input = cidrs::<IPv4>(&["10.0.0.0/24", "10.0.2.0/24", "10.0.3.0/25"]);
+
+        // merging 10.0.2.0/24 and 10.0.3.0/25 costs 128 addresses, merging
+        // everything in 10.0.0.0/22 costs 384
+        assert_eq!(
+            cidrs::<IPv4>(&["10.0.0.0/24", "10.0.2.0/23"]),
+            summarize_with_overcoverage(&input, 300)
+        );
+        assert_eq!(
+            cidrs::<IPv4>(&["10.0.0.0/22"]),
+            summarize_with_overcoverage(&input, 384)
+        );
+    }
+
+    #[test]
+    fn overcoverage_ipv6_whole_address_space() {
+        let input = cidrs::<IPv6>(&["::/128", "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff/128"]);
+
+        assert_eq!(input, summarize_with_overcoverage(&input, u128::MAX - 2));
+        assert_eq!(
+            cidrs::<IPv6>(&["::/0"]),
+            summarize_with_overcoverage(&input, u128::MAX - 1)
+        );
+    }
+}
@@ -0,0 +1,270 @@
+use crate::net::address_family::{all_ones, raw_from_u128};
+use crate::net::AddressFamily;
+use crate::net::IPv4;
+use crate::net::Mask;
+use crate::net::CIDR;
+use core::fmt;
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum WildcardMaskParsingError {
+    InvalidFormat,
+}
+
+/// ACL style wildcard mask where each bit set to 1 is ignored when matching
+/// an address. Unlike `Mask`, the bits don't have to be contiguous:
+/// `0.0.255.0` matches any value in the third octet.
+#[derive(PartialEq, Eq, Copy, Clone)]
+pub struct WildcardMask<A: AddressFamily = IPv4>(A::Raw);
+
+impl<A: AddressFamily> fmt::Debug for WildcardMask<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({:b})", self, self)
+    }
+}
+
+impl<A: AddressFamily> fmt::Display for WildcardMask<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", A::from_raw(self.0))
+    }
+}
+
+impl<A: AddressFamily> fmt::Binary for WildcardMask<A> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:b}", A::from_raw(self.0))
+    }
+}
+
+impl<A: AddressFamily> std::str::FromStr for WildcardMask<A> {
+    type Err = WildcardMaskParsingError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let address = s
+            .parse::<A>()
+            .map_err(|_| WildcardMaskParsingError::InvalidFormat)?;
+
+        Ok(Self::new(address))
+    }
+}
+
+impl<A: AddressFamily> From<Mask<A>> for WildcardMask<A> {
+    fn from(mask: Mask<A>) -> Self {
+        Self::from_bits(mask.wildcard().bits())
+    }
+}
+
+impl<A: AddressFamily> WildcardMask<A> {
+    pub fn new(wildcard: A) -> Self {
+        Self(wildcard.to_raw())
+    }
+
+    fn from_bits(bits: u128) -> Self {
+        Self(raw_from_u128::<A>(bits))
+    }
+
+    pub fn bits(&self) -> u128 {
+        self.0.into()
+    }
+
+    /// Equivalent netmask, with the ignored bits set to 0.
+    pub fn netmask(&self) -> A {
+        A::from_u128(!self.bits())
+    }
+
+    pub fn is_contiguous(&self) -> bool {
+        self.bits() & self.bits().wrapping_add(1) == 0
+    }
+
+    /// Converts back to a `Mask` when the wildcard is contiguous.
+    pub fn mask(&self) -> Option<Mask<A>> {
+        if !self.is_contiguous() {
+            return None;
+        }
+
+        Mask::new(A::BITS - self.bits().count_ones() as u8).ok()
+    }
+
+    pub fn matches(&self, base: &A, address: &A) -> bool {
+        (base.to_u128() ^ address.to_u128()) & !self.bits() & all_ones::<A>() == 0
+    }
+
+    /// Number of addresses matched, saturating at `u128::MAX`.
+    pub fn addresses(&self) -> u128 {
+        1u128
+            .checked_shl(self.bits().count_ones())
+            .unwrap_or(u128::MAX)
+    }
+
+    /// Lists every address matched by the wildcard, in ascending order.
+    pub fn matched_addresses(&self, base: &A) -> impl Iterator<Item = A> {
+        let first = base.to_u128() & !self.bits();
+
+        Self::combinations(self.bits()).map(move |bits| A::from_u128(first | bits))
+    }
+
+    /// Lists the smallest set of CIDRs matched by the wildcard, in ascending
+    /// order: the trailing ignored bits form the host part of each network and
+    /// the other ignored bits are enumerated.
+    pub fn matched_cidrs(&self, base: &A) -> impl Iterator<Item = CIDR<A>> {
+        let host_bits = self.bits().trailing_ones();
+        let first = base.to_u128() & !self.bits();
+        let mask = Mask::new(A::BITS - host_bits as u8).unwrap();
+
+        Self::combinations(self.network_bits())
+            .map(move |bits| CIDR::new(A::from_u128(first | bits), mask))
+    }
+
+    /// Number of CIDRs listed by `matched_cidrs`.
+    pub fn matched_cidrs_count(&self) -> u128 {
+        1u128 << self.network_bits().count_ones()
+    }
+
+    // Ignored bits that are not part of the trailing host part.
+    fn network_bits(&self) -> u128 {
+        let host_bits = self.bits().trailing_ones();
+        let host_part = u128::MAX.checked_shr(128 - host_bits).unwrap_or(0);
+
+        self.bits() & !host_part
+    }
+
+    // Iterates over every value that only uses the given bits, ascending.
+    fn combinations(bits: u128) -> impl Iterator<Item = u128> {
+        std::iter::successors(Some(0u128), move |current| {
+            if *current == bits {
+                return None;
+            }
+
+            Some((current | !bits).wrapping_add(1) & bits)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{WildcardMask, WildcardMaskParsingError};
+    use crate::net::{IPv4, IPv6, Mask, CIDR};
+
+    #[test]
+    fn parse_success() {
+        let wildcard = "0.0.255.0".parse::<WildcardMask>().unwrap();
+        assert_eq!(WildcardMask(0x0000FF00), wildcard);
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert_eq!(
+            Err(WildcardMaskParsingError::InvalidFormat),
+            "0.0.256.0".parse::<WildcardMask>()
+        );
+    }
+
+    #[test]
+    fn string_display() {
+        let wildcard = WildcardMask::new(IPv4::new(0, 255, 0, 3));
+        assert_eq!("0.255.0.3", format!("{}", wildcard));
+        assert_eq!(
+            "00000000.11111111.00000000.00000011",
+            format!("{:b}", wildcard)
+        );
+    }
+
+    #[test]
+    fn from_mask() {
+        let wildcard = WildcardMask::from(Mask::<IPv4>::new(20).unwrap());
+        assert_eq!(WildcardMask(0x00000FFF), wildcard);
+        assert!(wildcard.is_contiguous());
+        assert_eq!(Some(Mask::new(20).unwrap()), wildcard.mask());
+    }
+
+    #[test]
+    fn netmask() {
+        let wildcard = WildcardMask::new(IPv4::new(0, 0, 255, 0));
+        assert_eq!(IPv4::new(255, 255, 0, 255), wildcard.netmask());
+    }
+
+    #[test]
+    fn is_contiguous() {
+        assert!(WildcardMask::new(IPv4::new(0, 0, 0, 255)).is_contiguous());
+        assert!(WildcardMask::new(IPv4::new(0, 0, 0, 0)).is_contiguous());
+        assert!(WildcardMask::new(IPv4::new(255, 255, 255, 255)).is_contiguous());
+        assert!(!WildcardMask::new(IPv4::new(0, 0, 255, 0)).is_contiguous());
+        assert_eq!(None, WildcardMask::new(IPv4::new(0, 0, 255, 0)).mask());
+    }
+
+    #[test]
+    fn matches() {
+        let base = IPv4::new(10, 0, 0, 5);
+        let wildcard = WildcardMask::new(IPv4::new(0, 0, 255, 0));
+
+        assert!(wildcard.matches(&base, &IPv4::new(10, 0, 0, 5)));
+        assert!(wildcard.matches(&base, &IPv4::new(10, 0, 42, 5)));
+        assert!(wildcard.matches(&base, &IPv4::new(10, 0, 255, 5)));
+        assert!(!wildcard.matches(&base, &IPv4::new(10, 0, 42, 6)));
+        assert!(!wildcard.matches(&base, &IPv4::new(10, 1, 42, 5)));
+    }
+
+    #[test]
+    fn addresses() {
+        assert_eq!(1, WildcardMask::new(IPv4::new(0, 0, 0, 0)).addresses());
+        assert_eq!(256, WildcardMask::new(IPv4::new(0, 0, 255, 0)).addresses());
+        assert_eq!(512, WildcardMask::new(IPv4::new(0, 1, 255, 0)).addresses());
+        assert_eq!(
+            u128::MAX,
+            "ffff:ffff:ffff:ffff:ffff:ffff:ffff:ffff"
+                .parse::<WildcardMask<IPv6>>()
+                .unwrap()
+                .addresses()
+        );
+    }
+
+    #[test]
+    fn matched_addresses() {
+        let base = IPv4::new(10, 0, 0, 5);
+        let wildcard = WildcardMask::new(IPv4::new(0, 0, 2, 1));
+
+        let expected = vec![
+            IPv4::new(10, 0, 0, 4),
+            IPv4::new(10, 0, 0, 5),
+            IPv4::new(10, 0, 2, 4),
+            IPv4::new(10, 0, 2, 5),
+        ];
+        assert_eq!(
+            expected,
+            wildcard.matched_addresses(&base).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn matched_cidrs() {
+        let base = IPv4::new(10, 0, 0, 5);
+        let wildcard = WildcardMask::new(IPv4::new(0, 1, 0, 255));
+
+        let expected = vec![
+            CIDR::new(IPv4::new(10, 0, 0, 0), Mask::new(24).unwrap()),
+            CIDR::new(IPv4::new(10, 1, 0, 0), Mask::new(24).unwrap()),
+        ];
+        assert_eq!(expected, wildcard.matched_cidrs(&base).collect::<Vec<_>>());
+
+        let wildcard = WildcardMask::new(IPv4::new(0, 0, 0, 0));
+        let expected = vec![CIDR::new(base, Mask::new(32).unwrap())];
+        assert_eq!(expected, wildcard.matched_cidrs(&base).collect::<Vec<_>>());
+
+        let wildcard = WildcardMask::new(IPv4::new(255, 255, 255, 255));
+        let expected = vec![CIDR::new(IPv4::new(0, 0, 0, 0), Mask::new(0).unwrap())];
+        assert_eq!(expected, wildcard.matched_cidrs(&base).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn matched_cidrs_count() {
+        let wildcard = WildcardMask::new(IPv4::new(0, 1, 0, 255));
+        assert_eq!(2, wildcard.matched_cidrs_count());
+        let wildcard = WildcardMask::new(IPv4::new(255, 255, 255, 254));
+        assert_eq!(1 << 31, wildcard.matched_cidrs_count());
+        let wildcard = WildcardMask::new(IPv4::new(255, 255, 255, 255));
+        assert_eq!(1, wildcard.matched_cidrs_count());
+
+        let wildcard = "ffff:ffff:ffff:ffff:ffff:ffff:ffff:fffe"
+            .parse::<WildcardMask<IPv6>>()
+            .unwrap();
+        assert_eq!(1 << 127, wildcard.matched_cidrs_count());
+    }
+}